@@ -1,10 +1,313 @@
-use clap::Parser;
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
 
 /// A terminal-based portfolio viewer that fetches data from GitHub
 #[derive(Parser, Debug, Clone)]
 #[clap(author, version, about)]
 pub struct Args {
+    /// Command to run instead of the interactive viewer
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to the config file (local or GitHub URL)
-    #[clap(short, long, default_value = "https://github.com/Pokeylooted/Pokeylooted.github.io/blob/main/_config.yml")]
+    #[clap(short, long, global = true, default_value = "https://github.com/Pokeylooted/Pokeylooted.github.io/blob/main/_config.yml")]
     pub config_path: String,
-}
\ No newline at end of file
+
+    /// Draw icons with Nerd Font glyphs
+    #[clap(long, global = true, conflicts_with = "ascii")]
+    pub nerd_fonts: bool,
+
+    /// Use ASCII labels instead of Unicode icons
+    #[clap(long, global = true)]
+    pub ascii: bool,
+
+    /// Path to the settings file (defaults to <config dir>/portfolio-tui/settings.yml)
+    #[clap(long)]
+    pub settings: Option<String>,
+
+    /// Color theme: dark, light, high-contrast or a custom theme name
+    /// (defaults to the portfolio's darkmode setting)
+    #[clap(long, global = true)]
+    pub theme: Option<String>,
+
+    /// Disable mouse support, leaving text selection to the terminal
+    #[clap(long)]
+    pub no_mouse: bool,
+
+    /// Print the home card into the terminal and exit instead of opening
+    /// the fullscreen viewer
+    #[clap(long)]
+    pub inline: bool,
+
+    /// Height of the card printed by --inline, in lines
+    #[clap(long, default_value_t = 18, requires = "inline")]
+    pub inline_height: u16,
+
+    /// Key binding preset: default, vim or emacs (overrides the settings file)
+    #[clap(long)]
+    pub keymap: Option<String>,
+}
+
+/// Non-interactive commands
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Print the portfolio, or one section of it, as text
+    Print(PrintArgs),
+    /// Export the portfolio to another document format
+    Export(ExportArgs),
+    /// Render a view of the viewer off-screen and save it as an image or text
+    Screenshot(ScreenshotArgs),
+    /// Serve the portfolio to other machines
+    Serve(ServeArgs),
+}
+
+/// Options of the `print` command
+#[derive(ClapArgs, Debug, Clone)]
+pub struct PrintArgs {
+    /// Section to print, by title or navigation index (0 is Home); prints
+    /// everything when omitted
+    pub section: Option<String>,
+
+    /// Wrap text to this many columns (defaults to the terminal width, or 80
+    /// when not writing to a terminal)
+    #[clap(long)]
+    pub width: Option<usize>,
+
+    /// When to style the output with ANSI colors
+    #[clap(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+/// Options of the `export` command
+#[derive(ClapArgs, Debug, Clone)]
+pub struct ExportArgs {
+    /// Document format to write
+    #[clap(long, value_enum)]
+    pub format: ExportFormat,
+
+    /// File to write to (defaults to stdout)
+    #[clap(short, long)]
+    pub output: Option<String>,
+
+    /// Sections to include, by title and in this order (defaults to all)
+    #[clap(long, value_delimiter = ',')]
+    pub sections: Vec<String>,
+
+    /// Number of PDF columns; the second holds contacts and skills
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub columns: u8,
+
+    /// Start every section on a new PDF page
+    #[clap(long)]
+    pub section_breaks: bool,
+}
+
+/// Formats the portfolio can be exported to
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// README-style Markdown document
+    Markdown,
+    /// Self-contained HTML page with a print stylesheet
+    Html,
+    /// PDF resume with an embedded font
+    Pdf,
+    /// The portfolio as JSON, with the config's field names
+    Json,
+    /// Best-effort mapping to the jsonresume.org schema
+    JsonResume,
+    /// vCard 4.0 contact (.vcf) with email, website and social profiles
+    Vcard,
+    /// Gemini page (.gmi) with a link line for every URL
+    Gemtext,
+}
+
+/// Options of the `screenshot` command
+#[derive(ClapArgs, Debug, Clone)]
+pub struct ScreenshotArgs {
+    /// Section to show, by title or navigation index (0 is Home); defaults
+    /// to Home
+    pub section: Option<String>,
+
+    /// Screen size in character cells, as WIDTHxHEIGHT
+    #[clap(long, default_value = "100x30", value_parser = parse_size)]
+    pub size: (u16, u16),
+
+    /// Output format
+    #[clap(long, value_enum, default_value_t = ScreenshotFormat::Svg)]
+    pub format: ScreenshotFormat,
+
+    /// File to write to (defaults to stdout)
+    #[clap(short, long)]
+    pub output: Option<String>,
+}
+
+/// Formats a screenshot can be saved in
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    /// SVG image with the theme colors
+    Svg,
+    /// HTML page with a <pre> block of styled spans
+    Html,
+    /// Text with ANSI escape sequences
+    Ansi,
+}
+
+/// Options of the `serve` command
+#[derive(ClapArgs, Debug, Clone)]
+pub struct ServeArgs {
+    /// Protocol to serve
+    #[clap(subcommand)]
+    pub protocol: ServeProtocol,
+}
+
+/// Protocols the portfolio can be served over
+#[derive(Subcommand, Debug, Clone)]
+pub enum ServeProtocol {
+    /// Run the viewer for everyone who connects with `ssh`
+    Ssh(SshArgs),
+    /// Run the viewer in the browser through a web page and a WebSocket
+    Web(WebArgs),
+    /// Answer finger queries with the portfolio as text
+    Finger(FingerArgs),
+    /// Serve the portfolio as Gopher menus and text documents
+    Gopher(GopherArgs),
+    /// Serve the portfolio as a gemtext page over TLS
+    Gemini(GeminiArgs),
+}
+
+/// Options of `serve ssh`
+#[derive(ClapArgs, Debug, Clone)]
+pub struct SshArgs {
+    /// Address to listen on
+    #[clap(long, default_value = "0.0.0.0")]
+    pub bind: String,
+
+    /// Port to listen on
+    #[clap(long, default_value_t = 2222)]
+    pub port: u16,
+
+    /// Host key file, created when missing (defaults to
+    /// <config dir>/portfolio-tui/ssh_host_ed25519_key)
+    #[clap(long)]
+    pub host_key: Option<String>,
+
+    /// User names allowed to connect, comma separated (defaults to anyone)
+    #[clap(long, value_delimiter = ',')]
+    pub allow_users: Vec<String>,
+
+    /// Most visitors connected at the same time
+    #[clap(long, default_value_t = 32)]
+    pub max_connections: usize,
+}
+
+/// Options of `serve web`
+#[derive(ClapArgs, Debug, Clone)]
+pub struct WebArgs {
+    /// Address to listen on
+    #[clap(long, default_value = "0.0.0.0")]
+    pub bind: String,
+
+    /// Port to listen on
+    #[clap(long, default_value_t = 8080)]
+    pub port: u16,
+
+    /// Most visitors connected at the same time
+    #[clap(long, default_value_t = 32)]
+    pub max_connections: usize,
+
+    /// Seconds without input before a visitor is disconnected
+    #[clap(long, default_value_t = 600)]
+    pub idle_timeout: u64,
+}
+
+/// Options of `serve finger`
+#[derive(ClapArgs, Debug, Clone)]
+pub struct FingerArgs {
+    /// Address to listen on
+    #[clap(long, default_value = "0.0.0.0")]
+    pub bind: String,
+
+    /// Port to listen on (finger clients connect to 79)
+    #[clap(long, default_value_t = 7979)]
+    pub port: u16,
+
+    /// Most connections handled at the same time
+    #[clap(long, default_value_t = 32)]
+    pub max_connections: usize,
+}
+
+/// Options of `serve gopher`
+#[derive(ClapArgs, Debug, Clone)]
+pub struct GopherArgs {
+    /// Address to listen on
+    #[clap(long, default_value = "0.0.0.0")]
+    pub bind: String,
+
+    /// Port to listen on (Gopher clients default to 70)
+    #[clap(long, default_value_t = 7070)]
+    pub port: u16,
+
+    /// Host name clients reach the server by, used in menu links
+    #[clap(long, default_value = "localhost")]
+    pub hostname: String,
+
+    /// Port clients reach the server on, used in menu links (defaults to
+    /// --port)
+    #[clap(long)]
+    pub public_port: Option<u16>,
+
+    /// Most connections handled at the same time
+    #[clap(long, default_value_t = 32)]
+    pub max_connections: usize,
+}
+
+/// Options of `serve gemini`
+#[derive(ClapArgs, Debug, Clone)]
+pub struct GeminiArgs {
+    /// Address to listen on
+    #[clap(long, default_value = "0.0.0.0")]
+    pub bind: String,
+
+    /// Port to listen on
+    #[clap(long, default_value_t = 1965)]
+    pub port: u16,
+
+    /// Host name clients reach the server by, used for the generated
+    /// certificate
+    #[clap(long, default_value = "localhost")]
+    pub hostname: String,
+
+    /// PEM certificate chain to use instead of a generated self-signed one
+    #[clap(long, requires = "key")]
+    pub cert: Option<String>,
+
+    /// PEM private key of --cert
+    #[clap(long, requires = "cert")]
+    pub key: Option<String>,
+
+    /// Most connections handled at the same time
+    #[clap(long, default_value_t = 32)]
+    pub max_connections: usize,
+}
+
+/// Parse a size written as `WIDTHxHEIGHT`
+fn parse_size(size: &str) -> Result<(u16, u16), String> {
+    let invalid = || format!("expected WIDTHxHEIGHT like 100x30, got '{}'", size);
+    let (width, height) = size.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width: u16 = width.trim().parse().map_err(|_| invalid())?;
+    let height: u16 = height.trim().parse().map_err(|_| invalid())?;
+    if width < 20 || height < 5 {
+        return Err(format!("{}x{} is too small, the smallest size is 20x5", width, height));
+    }
+    Ok((width, height))
+}
+
+/// When to write ANSI colors
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Only when writing to a terminal
+    Auto,
+    /// Always
+    Always,
+    /// Never
+    Never,
+}
@@ -2,3 +2,4 @@ pub mod args;
 pub mod settings;
 
 pub use args::Args;
+pub use settings::Settings;
@@ -1,23 +1,82 @@
-//! Settings module for future use
-//! 
-//! This module provides settings management capabilities for the application.
-//! It is currently not used but is kept for future enhancements.
-//! The warnings are suppressed with the #[allow(dead_code)] attribute.
+//! Settings file handling
+//!
+//! Settings are read from a YAML file, by default
+//! `<config dir>/portfolio-tui/settings.yml`. Every field is optional.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Application settings
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
 pub struct Settings {
-    /// Path to the config file
-    pub config_path: String,
+    /// Key bindings
+    pub keymap: KeymapSettings,
+    /// Capture the mouse (defaults to true); `false` is the same as
+    /// `--no-mouse`
+    pub mouse: Option<bool>,
+}
+
+/// Key binding settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct KeymapSettings {
+    /// Preset to start from (`default`, `vim` or `emacs`)
+    pub preset: Option<String>,
+    /// Per-action overrides, replacing the preset's keys for that action
+    pub bindings: BTreeMap<String, KeyList>,
+}
+
+/// One or more key chords
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    /// A single key chord
+    One(String),
+    /// A list of key chords
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    /// All key chords in the list
+    pub fn chords(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(chord) => vec![chord.as_str()],
+            KeyList::Many(chords) => chords.iter().map(String::as_str).collect(),
+        }
+    }
 }
 
 impl Settings {
-    /// Create new settings from command line arguments
-    #[allow(dead_code)]
-    pub fn new(config_path: String) -> Result<Self> {
-        Ok(Self { config_path })
+    /// Load settings from `path`, or from the default location if it exists
+    pub fn load(path: Option<&str>) -> Result<Self> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => match Self::default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+        Self::from_file(&path)
     }
-}
\ No newline at end of file
+
+    /// Read settings from a file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Could not read settings file {}", path.display()))?;
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid settings file {}", path.display()))
+    }
+
+    /// Directory holding the settings file and other user configuration
+    pub fn config_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("portfolio-tui"))
+    }
+
+    /// Default settings file location
+    pub fn default_path() -> Option<PathBuf> {
+        Self::config_dir().map(|dir| dir.join("settings.yml"))
+    }
+}
@@ -0,0 +1,65 @@
+//! Cache of the last successfully loaded data
+//!
+//! The raw content of every source that loads and parses is kept in
+//! `<cache dir>/portfolio-tui/`, so the viewer can fall back to it when the
+//! source is unavailable.
+
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Cached content of a source
+#[derive(Debug, Clone)]
+pub struct Cached {
+    /// Raw content
+    pub content: String,
+    /// When the content was saved
+    pub saved: SystemTime,
+}
+
+/// On-disk cache of loaded sources
+pub struct Cache {
+    dir: Option<PathBuf>,
+}
+
+impl Cache {
+    /// Create a cache in the user's cache directory
+    pub fn new() -> Self {
+        Self {
+            dir: dirs::cache_dir().map(|dir| dir.join("portfolio-tui")),
+        }
+    }
+
+    /// Save the content loaded from a source
+    pub fn store(&self, source: &str, content: &str) -> Result<()> {
+        let path = self.path(source).ok_or_else(|| anyhow!("No cache directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// When the content of a source was last saved, if it is cached
+    pub fn saved(&self, source: &str) -> Option<SystemTime> {
+        fs::metadata(self.path(source)?).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    /// Load the cached content of a source
+    pub fn load(&self, source: &str) -> Option<Cached> {
+        let path = self.path(source)?;
+        let content = fs::read_to_string(&path).ok()?;
+        let saved = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()?;
+        Some(Cached { content, saved })
+    }
+
+    /// Cache file for a source, named by a stable hash of the source
+    fn path(&self, source: &str) -> Option<PathBuf> {
+        // FNV-1a, so file names stay the same across builds
+        let hash = source
+            .bytes()
+            .fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+        self.dir.as_ref().map(|dir| dir.join(format!("{:016x}.yml", hash)))
+    }
+}
@@ -0,0 +1,110 @@
+//! Load error classification
+//!
+//! Turns the errors from fetching and parsing into categories the error
+//! screen can explain.
+
+use std::io;
+
+/// Why the portfolio could not be loaded
+#[derive(Debug, Clone)]
+pub enum LoadError {
+    /// The server could not be reached
+    Network(String),
+    /// The server answered with an error status
+    Http {
+        /// HTTP status code
+        status: u16,
+        /// URL that failed
+        url: String,
+    },
+    /// The local file does not exist
+    NotFound(String),
+    /// The local file could not be read
+    Io(String),
+    /// The data is not a valid portfolio
+    Parse {
+        /// Parser message
+        message: String,
+        /// Line and column of the problem, 1-based
+        location: Option<(usize, usize)>,
+        /// Offending line of the input
+        snippet: Option<String>,
+    },
+    /// The user cancelled the load
+    Cancelled,
+}
+
+impl LoadError {
+    /// Classify an error from fetching `source`
+    pub fn from_fetch(err: &anyhow::Error, source: &str) -> Self {
+        for cause in err.chain() {
+            if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+                return match err.status() {
+                    Some(status) => LoadError::Http {
+                        status: status.as_u16(),
+                        url: err.url().map_or_else(|| source.to_string(), |url| url.to_string()),
+                    },
+                    None => LoadError::Network(format!("{:#}", err)),
+                };
+            }
+            if let Some(err) = cause.downcast_ref::<io::Error>() {
+                return match err.kind() {
+                    io::ErrorKind::NotFound => LoadError::NotFound(source.to_string()),
+                    _ => LoadError::Io(err.to_string()),
+                };
+            }
+        }
+        LoadError::Io(format!("{:#}", err))
+    }
+
+    /// Classify an error from parsing `content`
+    pub fn from_parse(err: &anyhow::Error, content: &str) -> Self {
+        let yaml = err.chain().find_map(|cause| cause.downcast_ref::<serde_yaml::Error>());
+        let location = yaml.and_then(|err| err.location()).map(|location| (location.line(), location.column()));
+        let snippet = location
+            .and_then(|(line, _)| content.lines().nth(line.saturating_sub(1)))
+            .map(|line| line.trim_end().to_string());
+        LoadError::Parse {
+            message: yaml.map_or_else(|| format!("{:#}", err), |err| err.to_string()),
+            location,
+            snippet,
+        }
+    }
+
+    /// Short description of the category
+    pub fn title(&self) -> &'static str {
+        match self {
+            LoadError::Network(_) => "Network error",
+            LoadError::Http { .. } => "Server error",
+            LoadError::NotFound(_) => "File not found",
+            LoadError::Io(_) => "Could not read the portfolio",
+            LoadError::Parse { .. } => "Invalid portfolio data",
+            LoadError::Cancelled => "Loading cancelled",
+        }
+    }
+
+    /// Explanation lines shown below the title
+    pub fn details(&self) -> Vec<String> {
+        match self {
+            LoadError::Network(message) => vec![
+                "The server could not be reached. Check your connection.".to_string(),
+                message.clone(),
+            ],
+            LoadError::Http { status, url } => vec![format!("{} answered with HTTP {}", url, status)],
+            LoadError::NotFound(path) => vec![format!("There is no file at {}", path)],
+            LoadError::Io(message) => vec![message.clone()],
+            LoadError::Parse { message, location, snippet } => {
+                let mut lines = Vec::new();
+                if let Some((line, column)) = location {
+                    lines.push(format!("Line {}, column {}:", line, column));
+                }
+                if let Some(snippet) = snippet {
+                    lines.push(snippet.clone());
+                }
+                lines.push(message.clone());
+                lines
+            }
+            LoadError::Cancelled => vec!["Loading was cancelled before it finished.".to_string()],
+        }
+    }
+}
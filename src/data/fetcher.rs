@@ -2,6 +2,13 @@ use anyhow::Result;
 use reqwest::Client;
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
+
+/// How long connecting to a server may take
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a whole request may take, so a stalled server cannot hang a load
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Fetches data from a source (GitHub or local file)
 pub struct Fetcher {
@@ -11,9 +18,12 @@ pub struct Fetcher {
 impl Fetcher {
     /// Create a new fetcher
     pub fn new() -> Self {
-        Self {
-            client: Client::new(),
-        }
+        let client = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self { client }
     }
 
     /// Fetch data from a source
@@ -36,7 +46,7 @@ impl Fetcher {
         };
 
         // Fetch the content
-        let response = self.client.get(&raw_url).send().await?;
+        let response = self.client.get(&raw_url).send().await?.error_for_status()?;
         let content = response.text().await?;
         Ok(content)
     }
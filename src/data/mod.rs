@@ -1,5 +1,15 @@
+pub mod cache;
+pub mod error;
 pub mod fetcher;
 pub mod models;
 pub mod parser;
 
-pub use models::Portfolio;
\ No newline at end of file
+pub use models::Portfolio;
+
+use anyhow::Result;
+
+/// Fetch and parse the portfolio from a local file or URL
+pub async fn load(source: &str) -> Result<Portfolio> {
+    let content = fetcher::Fetcher::new().fetch(source).await?;
+    parser::Parser::new().parse(&content)
+}
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Fields the app does not know about, kept so exports preserve them
+pub type Extra = BTreeMap<String, serde_yaml::Value>;
 
 /// Portfolio data model
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -7,8 +11,8 @@ pub struct Portfolio {
     pub repository: Option<String>,
     /// Favicon
     pub favicon: Option<String>,
-    /// Version
-    pub version: Option<String>,
+    /// Version, kept as written so a number stays a number in exports
+    pub version: Option<serde_yaml::Value>,
     /// Name
     pub name: Option<String>,
     /// Title
@@ -37,6 +41,9 @@ pub struct Portfolio {
     pub about_content: Option<String>,
     /// Content sections
     pub content: Option<Vec<ContentSection>>,
+    /// Fields not used by the app
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Additional link
@@ -48,6 +55,9 @@ pub struct AdditionalLink {
     pub icon: Option<String>,
     /// URL
     pub url: Option<String>,
+    /// Fields not used by the app
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Content section
@@ -58,12 +68,15 @@ pub struct ContentSection {
     /// Layout
     pub layout: Option<String>,
     /// Content items - can be either a list of items or a string
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "ContentValue::is_empty")]
     pub content: ContentValue,
+    /// Fields not used by the app
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Content value - can be either a list of items or a string
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[serde(untagged)]
 pub enum ContentValue {
     /// List of content items
@@ -72,12 +85,14 @@ pub enum ContentValue {
     Text(String),
     /// Empty content
     #[serde(skip_deserializing)]
+    #[default]
     Empty,
 }
 
-impl Default for ContentValue {
-    fn default() -> Self {
-        ContentValue::Empty
+impl ContentValue {
+    /// Whether the section has no content
+    pub fn is_empty(&self) -> bool {
+        matches!(self, ContentValue::Empty)
     }
 }
 
@@ -104,11 +119,15 @@ pub struct ContentItem {
     pub link: Option<String>,
     /// Additional links
     pub additional_links: Option<Vec<AdditionalLink>>,
+    /// Fields not used by the app
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 // For compatibility with the existing code
 /// Personal information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[allow(dead_code)]
 pub struct Personal {
     /// Name
     pub name: String,
@@ -122,6 +141,7 @@ pub struct Personal {
 
 /// Project information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[allow(dead_code)]
 pub struct Project {
     /// Project name
     pub name: String,
@@ -135,6 +155,7 @@ pub struct Project {
 
 /// Skill information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[allow(dead_code)]
 pub struct Skill {
     /// Skill name
     pub name: String,
@@ -146,6 +167,7 @@ pub struct Skill {
 
 /// Social link
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[allow(dead_code)]
 pub struct Social {
     /// Platform name
     pub platform: String,
@@ -0,0 +1,218 @@
+//! Gemtext export
+//!
+//! Writes the portfolio as a Gemini page (`text/gemini`): a heading per
+//! section and item, and a `=>` line for every URL. Gemtext has no inline
+//! links or emphasis, so Markdown in descriptions is reduced to plain text
+//! and its links are listed on their own lines after the paragraph.
+
+use regex::Regex;
+use std::fmt::Write;
+
+use super::absolute_url;
+use super::markdown::clean_markdown;
+use crate::data::models::{ContentItem, ContentSection, ContentValue, Portfolio};
+use crate::processor::formatter::FormattedPortfolio;
+
+/// Render the portfolio as gemtext
+pub fn render(portfolio: &Portfolio, formatted: &FormattedPortfolio) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", formatted.name);
+    let _ = writeln!(out, "{}\n", formatted.title);
+
+    let contacts = formatted
+        .social
+        .iter()
+        .map(|social| {
+            let label = if social.platform == "Email" { &social.username } else { &social.platform };
+            (social.url.as_str(), label.as_str())
+        })
+        .chain(formatted.links.iter().map(|link| (link.url.as_str(), link.title.as_str())));
+    let mut any = false;
+    for (url, label) in contacts {
+        write_link(&mut out, url, label);
+        any = true;
+    }
+    if any {
+        let _ = writeln!(out);
+    }
+
+    if let Some(ref about) = portfolio.about_content {
+        let _ = writeln!(out, "## About\n");
+        write_text(&mut out, about);
+    }
+
+    for section in portfolio.content.iter().flatten() {
+        write_section(&mut out, section);
+    }
+    format!("{}\n", out.trim_end())
+}
+
+/// Write a section according to its layout
+fn write_section(out: &mut String, section: &ContentSection) {
+    if let Some(ref title) = section.title {
+        let _ = writeln!(out, "## {}\n", title);
+    }
+    match section.content {
+        ContentValue::Text(ref text) => write_text(out, text),
+        ContentValue::Items(ref items) => {
+            for item in items {
+                write_item(out, item);
+            }
+        }
+        ContentValue::Empty => {}
+    }
+}
+
+/// Write an item: title, subtitle and caption, quote, description and links
+fn write_item(out: &mut String, item: &ContentItem) {
+    if let Some(ref title) = item.title {
+        let _ = writeln!(out, "### {}\n", title);
+    }
+    let meta: Vec<&str> = [item.sub_title.as_deref(), item.caption.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect();
+    if !meta.is_empty() {
+        let _ = writeln!(out, "{}\n", meta.join(" · "));
+    }
+    if let Some(ref quote) = item.quote {
+        for line in quote.trim().lines() {
+            let _ = writeln!(out, "> {}", line.trim());
+        }
+        let _ = writeln!(out);
+    }
+    if let Some(ref description) = item.description {
+        write_text(out, description);
+    }
+
+    // The item's own link is often repeated among its additional links
+    let title = item.title.as_deref().unwrap_or("Link");
+    let mut links: Vec<(&str, &str)> = Vec::new();
+    let own = [item.url.as_deref(), item.link.as_deref()].into_iter().flatten().map(|url| (url, title));
+    let additional = item
+        .additional_links
+        .iter()
+        .flatten()
+        .filter_map(|link| link.url.as_deref().map(|url| (url, link.title.as_deref().unwrap_or(url))));
+    for (url, label) in own.chain(additional) {
+        if !links.iter().any(|(seen, _)| absolute_url(seen) == absolute_url(url)) {
+            links.push((url, label));
+        }
+    }
+    for &(url, label) in &links {
+        write_link(out, url, label);
+    }
+    if !links.is_empty() {
+        let _ = writeln!(out);
+    }
+}
+
+/// Write a link line
+fn write_link(out: &mut String, url: &str, label: &str) {
+    let _ = writeln!(out, "=> {} {}", absolute_url(url.trim()), label.trim());
+}
+
+/// Write Markdown text as gemtext
+///
+/// Paragraphs become single lines, list items `*` lines and quotes `>`
+/// lines. Links in a paragraph follow it as link lines.
+fn write_text(out: &mut String, markdown: &str) {
+    let link = Regex::new(r"\[([^\]]*)\]\(([^)\s]+)\)").unwrap();
+    // Bare URLs end before punctuation closing a sentence and emphasis
+    let bare = Regex::new(r"<?(https?://[^\s<>)\]*]*[^\s<>)\]*.,;:!?])>?").unwrap();
+    let emphasis = Regex::new(r"\*\*|__|`|\*([^*\s][^*]*)\*").unwrap();
+
+    for block in clean_markdown(markdown).split("\n\n") {
+        let mut links = Vec::new();
+        let mut inline = |text: &str| {
+            let text = link.replace_all(text, |captures: &regex::Captures| {
+                links.push((captures[2].to_string(), captures[1].to_string()));
+                captures[1].to_string()
+            });
+            for captures in bare.captures_iter(&text) {
+                links.push((captures[1].to_string(), captures[1].to_string()));
+            }
+            let text = bare.replace_all(&text, "$1");
+            emphasis.replace_all(&text, "$1").trim().to_string()
+        };
+
+        let lines: Vec<&str> = block.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        if lines.is_empty() {
+            continue;
+        }
+        if lines.iter().all(|line| is_list_item(line)) {
+            for line in &lines {
+                let _ = writeln!(out, "* {}", inline(&line[2..]));
+            }
+        } else if lines.iter().all(|line| line.starts_with('>')) {
+            for line in &lines {
+                let _ = writeln!(out, "> {}", inline(line.trim_start_matches('>')));
+            }
+        } else {
+            // Gemtext has three heading levels, all used by the page itself,
+            // so headings in the text stay plain lines of their own
+            let mut paragraphs = vec![Vec::new()];
+            for line in &lines {
+                match line.strip_prefix('#') {
+                    Some(heading) => paragraphs.extend([vec![heading.trim_start_matches('#')], Vec::new()]),
+                    None => paragraphs.last_mut().unwrap().push(*line),
+                }
+            }
+            for paragraph in paragraphs.iter().filter(|paragraph| !paragraph.is_empty()) {
+                let _ = writeln!(out, "{}", inline(&paragraph.join(" ")));
+            }
+        }
+        for (url, label) in links {
+            write_link(out, &url, &label);
+        }
+        let _ = writeln!(out);
+    }
+}
+
+/// Whether a line is a Markdown list item
+fn is_list_item(line: &str) -> bool {
+    ["- ", "* ", "+ "].iter().any(|marker| line.starts_with(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::parser::Parser;
+    use crate::processor::Formatter;
+
+    fn export(yaml: &str) -> String {
+        let portfolio = Parser::new().parse(yaml).unwrap();
+        render(&portfolio, &Formatter::new().format(&portfolio))
+    }
+
+    /// Link lines of a page
+    fn links(gemtext: &str) -> Vec<&str> {
+        gemtext.lines().filter(|line| line.starts_with("=>")).collect()
+    }
+
+    #[test]
+    fn every_link_gets_one_line() {
+        let gemtext = export(
+            "name: Test\nwebsite: example.com\n\
+             content:\n  - title: Projects\n    content:\n      - title: Thing\n        url: thing.example.com\n\
+             \x20       additional_links:\n          - title: Source\n            url: https://thing.example.com\n\
+             \x20         - title: Docs\n            url: docs.example.com\n",
+        );
+        assert_eq!(
+            links(&gemtext),
+            [
+                "=> https://example.com Website",
+                "=> https://thing.example.com Thing",
+                "=> https://docs.example.com Docs",
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_links_follow_their_paragraph() {
+        let gemtext = export("name: Test\nabout_content: \"See [my blog](blog.example.com) or **https://example.org**.\"\n");
+        assert!(gemtext.contains("See my blog or https://example.org.\n=> https://blog.example.com my blog\n=> https://example.org https://example.org\n"));
+    }
+}
@@ -0,0 +1,205 @@
+//! HTML export
+//!
+//! Renders a single self-contained page with inline CSS and no external
+//! assets. The page and its stylesheet come from built-in templates, which
+//! can be replaced by `resume.html` and `style.css` in
+//! `<config dir>/portfolio-tui/templates/`. Templates use Jinja syntax.
+
+use anyhow::{Context, Result};
+use minijinja::Environment;
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use super::absolute_url;
+use crate::data::models::{ContentItem, ContentValue, Portfolio};
+use crate::processor::formatter::FormattedPortfolio;
+
+/// Built-in page template
+const RESUME_TEMPLATE: &str = include_str!("templates/resume.html");
+
+/// Built-in stylesheet
+const STYLE: &str = include_str!("templates/style.css");
+
+/// Data handed to the page template
+#[derive(Debug, Serialize)]
+struct Page {
+    name: String,
+    title: String,
+    darkmode: bool,
+    contacts: Vec<Link>,
+    /// About text rendered to HTML
+    about: Option<String>,
+    sections: Vec<Section>,
+    /// Stylesheet to inline
+    style: String,
+}
+
+/// A content section
+#[derive(Debug, Serialize)]
+struct Section {
+    title: Option<String>,
+    layout: Option<String>,
+    /// Text of a text layout section rendered to HTML
+    text: Option<String>,
+    items: Vec<Item>,
+}
+
+/// A content item
+#[derive(Debug, Serialize)]
+struct Item {
+    title: Option<String>,
+    sub_title: Option<String>,
+    caption: Option<String>,
+    url: Option<String>,
+    quote: Option<String>,
+    /// Description rendered to HTML
+    description: Option<String>,
+    links: Vec<Link>,
+}
+
+/// A labelled link
+#[derive(Debug, Serialize)]
+struct Link {
+    label: String,
+    url: String,
+}
+
+/// Render the portfolio as an HTML page
+///
+/// Templates in `template_dir` replace the built-in ones of the same name.
+pub fn render(portfolio: &Portfolio, formatted: &FormattedPortfolio, template_dir: Option<&Path>) -> Result<String> {
+    let template = read_template(template_dir, "resume.html")?.unwrap_or_else(|| RESUME_TEMPLATE.to_string());
+    let style = read_template(template_dir, "style.css")?.unwrap_or_else(|| STYLE.to_string());
+
+    let contacts = formatted
+        .social
+        .iter()
+        .map(|social| Link {
+            label: if social.platform == "Email" { social.username.clone() } else { social.platform.clone() },
+            url: absolute_url(&social.url),
+        })
+        .chain(formatted.links.iter().map(|link| Link {
+            label: link.title.clone(),
+            url: absolute_url(&link.url),
+        }))
+        .collect();
+
+    let sections = portfolio
+        .content
+        .iter()
+        .flatten()
+        .map(|section| {
+            let (text, items) = match section.content {
+                ContentValue::Text(ref text) => (Some(markdown_to_html(text)), Vec::new()),
+                ContentValue::Items(ref items) => (None, items.iter().map(item).collect()),
+                ContentValue::Empty => (None, Vec::new()),
+            };
+            Section {
+                title: section.title.clone(),
+                layout: section.layout.clone(),
+                text,
+                items,
+            }
+        })
+        .collect();
+
+    let page = Page {
+        name: formatted.name.clone(),
+        title: formatted.title.clone(),
+        darkmode: portfolio.darkmode.unwrap_or(true),
+        contacts,
+        about: portfolio.about_content.as_deref().map(markdown_to_html),
+        sections,
+        style,
+    };
+
+    let mut env = Environment::new();
+    env.add_template("resume.html", &template).context("Invalid HTML template")?;
+    let html = env
+        .get_template("resume.html")?
+        .render(&page)
+        .context("Could not render the HTML template")?;
+    Ok(html)
+}
+
+/// Template data for a content item
+fn item(item: &ContentItem) -> Item {
+    Item {
+        title: item.title.clone(),
+        sub_title: item.sub_title.clone(),
+        caption: item.caption.clone(),
+        url: item.url.as_deref().or(item.link.as_deref()).map(absolute_url),
+        quote: item.quote.as_deref().map(|quote| quote.trim().to_string()),
+        description: item.description.as_deref().map(markdown_to_html),
+        links: item
+            .additional_links
+            .iter()
+            .flatten()
+            .filter_map(|link| {
+                let url = link.url.as_deref()?;
+                Some(Link {
+                    label: link.title.clone().unwrap_or_else(|| url.to_string()),
+                    url: absolute_url(url),
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Read a template override, if the user has one
+fn read_template(dir: Option<&Path>, name: &str) -> Result<Option<String>> {
+    let Some(path) = dir.map(|dir| dir.join(name)).filter(|path| path.is_file()) else {
+        return Ok(None);
+    };
+    let content = fs::read_to_string(&path).with_context(|| format!("Could not read template {}", path.display()))?;
+    Ok(Some(content))
+}
+
+/// Render Markdown to HTML, leaving out images so the page has no external assets
+fn markdown_to_html(text: &str) -> String {
+    let mut in_image = false;
+    let events = Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES).filter(|event| {
+        match event {
+            Event::Start(Tag::Image { .. }) => in_image = true,
+            Event::End(TagEnd::Image) => {
+                in_image = false;
+                return false;
+            }
+            _ => {}
+        }
+        !in_image
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::parser::Parser;
+    use crate::processor::Formatter;
+
+    fn export(yaml: &str) -> String {
+        let portfolio = Parser::new().parse(yaml).unwrap();
+        render(&portfolio, &Formatter::new().format(&portfolio), None).unwrap()
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let html = export("name: A <b>\nabout_content: Uses 1 < 2 & more\n");
+        assert!(html.contains("<h1>A &lt;b&gt;</h1>"));
+        assert!(html.contains("Uses 1 &lt; 2 &amp; more"));
+    }
+
+    #[test]
+    fn contact_links_are_absolute() {
+        let html = export("name: Test\nwebsite: example.com\nadditional_links:\n  - title: Blog\n    url: blog.example.com\n");
+        // The template escapes `/` in attributes, which browsers read back
+        let html = html.replace("&#x2f;", "/");
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("href=\"https://blog.example.com\""));
+    }
+}
@@ -0,0 +1,102 @@
+//! JSON export
+//!
+//! Writes the parsed portfolio as JSON with the same field names as the YAML
+//! config, including fields the app does not use. JSON is valid YAML, so the
+//! output can be loaded back with `--config-path`.
+
+use anyhow::{Context, Result};
+
+use crate::data::models::Portfolio;
+
+/// Render the portfolio as pretty-printed JSON
+pub fn render(portfolio: &Portfolio) -> Result<String> {
+    let json = serde_json::to_string_pretty(portfolio).context("Could not convert the portfolio to JSON")?;
+    Ok(format!("{}\n", json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::models::{ContentItem, ContentValue};
+    use crate::data::parser::Parser;
+
+    /// Config with fields the app does not know at every level
+    const YAML: &str = r##"
+version: 2
+name: Test
+theme_color: "#ffcc00"
+build:
+  count: 3
+  ok: true
+additional_links:
+  - title: Blog
+    url: blog.example.com
+    rel: me
+content:
+  - title: Projects
+    weight: 1.5
+    content:
+      - title: Thing
+        tags: [rust, tui]
+        additional_links:
+          - title: Source
+            url: git.example.com/thing
+            order: 1
+  - title: About
+    content: Plain text
+    hidden: false
+"##;
+
+    /// Parse YAML, export it as JSON and parse the JSON back
+    fn round_trip(yaml: &str) -> (Portfolio, Portfolio) {
+        let parser = Parser::new();
+        let original = parser.parse(yaml).unwrap();
+        let reloaded = parser.parse(&render(&original).unwrap()).unwrap();
+        (original, reloaded)
+    }
+
+    #[test]
+    fn round_trip_keeps_unknown_fields() {
+        let (original, reloaded) = round_trip(YAML);
+        assert_eq!(original.extra.len(), 2);
+        assert_eq!(reloaded.extra, original.extra);
+
+        let links = |portfolio: &Portfolio| portfolio.additional_links.clone().unwrap();
+        assert_eq!(links(&reloaded)[0].extra, links(&original)[0].extra);
+        assert!(links(&reloaded)[0].extra.contains_key("rel"));
+
+        let sections = |portfolio: &Portfolio| portfolio.content.clone().unwrap();
+        for (before, after) in sections(&original).iter().zip(&sections(&reloaded)) {
+            assert!(!before.extra.is_empty());
+            assert_eq!(after.extra, before.extra);
+        }
+        let (ContentValue::Items(before), ContentValue::Items(after)) =
+            (&sections(&original)[0].content, &sections(&reloaded)[0].content)
+        else {
+            panic!("the first section should have items");
+        };
+        assert_eq!(after[0].extra, before[0].extra);
+        let link = |items: &[ContentItem]| items[0].additional_links.clone().unwrap()[0].extra.clone();
+        assert_eq!(link(after), link(before));
+        assert!(matches!(sections(&reloaded)[1].content, ContentValue::Text(ref text) if text == "Plain text"));
+    }
+
+    #[test]
+    fn round_trip_is_stable() {
+        let (original, reloaded) = round_trip(YAML);
+        assert_eq!(render(&reloaded).unwrap(), render(&original).unwrap());
+    }
+
+    #[test]
+    fn scalars_keep_their_type() {
+        let (_, reloaded) = round_trip(YAML);
+        let json: serde_json::Value = serde_json::from_str(&render(&reloaded).unwrap()).unwrap();
+        assert_eq!(json["version"], serde_json::json!(2));
+        assert_eq!(json["build"], serde_json::json!({ "count": 3, "ok": true }));
+        assert_eq!(json["content"][0]["weight"], serde_json::json!(1.5));
+
+        let (_, reloaded) = round_trip("version: \"1.0\"\n");
+        let json: serde_json::Value = serde_json::from_str(&render(&reloaded).unwrap()).unwrap();
+        assert_eq!(json["version"], serde_json::json!("1.0"));
+    }
+}
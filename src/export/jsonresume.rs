@@ -0,0 +1,268 @@
+//! JSON Resume export
+//!
+//! Maps the portfolio onto the [JSON Resume](https://jsonresume.org/schema)
+//! schema. Sections are matched to resume sections by title, like
+//! "Experience" to `work`; sections without an equivalent are left out and
+//! reported. Date ranges are read from item captions such as `2021 - Present`.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+
+use super::absolute_url;
+use super::markdown::clean_markdown;
+use crate::data::models::{ContentItem, ContentValue, Portfolio};
+use crate::processor::formatter::FormattedPortfolio;
+
+/// JSON Resume section a content section maps to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Work,
+    Volunteer,
+    Education,
+    Awards,
+    Certificates,
+    Publications,
+    Projects,
+    References,
+    Interests,
+}
+
+impl Kind {
+    /// Words in a section title that select each kind, checked in order
+    const TITLE_WORDS: &[(&'static str, Kind)] = &[
+        ("volunteer", Kind::Volunteer),
+        ("experience", Kind::Work),
+        ("work", Kind::Work),
+        ("employment", Kind::Work),
+        ("career", Kind::Work),
+        ("education", Kind::Education),
+        ("school", Kind::Education),
+        ("award", Kind::Awards),
+        ("honor", Kind::Awards),
+        ("honour", Kind::Awards),
+        ("certific", Kind::Certificates),
+        ("publication", Kind::Publications),
+        ("project", Kind::Projects),
+        ("testimonial", Kind::References),
+        ("reference", Kind::References),
+        ("recommendation", Kind::References),
+        ("interest", Kind::Interests),
+        ("hobb", Kind::Interests),
+    ];
+
+    fn from_title(title: &str) -> Option<Self> {
+        let title = title.to_lowercase();
+        Self::TITLE_WORDS
+            .iter()
+            .find(|(word, _)| title.contains(word))
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Key of the section in the resume
+    fn key(self) -> &'static str {
+        match self {
+            Kind::Work => "work",
+            Kind::Volunteer => "volunteer",
+            Kind::Education => "education",
+            Kind::Awards => "awards",
+            Kind::Certificates => "certificates",
+            Kind::Publications => "publications",
+            Kind::Projects => "projects",
+            Kind::References => "references",
+            Kind::Interests => "interests",
+        }
+    }
+}
+
+/// Render the portfolio as a JSON Resume document
+pub fn render(portfolio: &Portfolio, formatted: &FormattedPortfolio) -> Result<String> {
+    let mut resume = Map::new();
+    resume.insert(
+        "$schema".to_string(),
+        json!("https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json"),
+    );
+    resume.insert("basics".to_string(), basics(portfolio, formatted));
+
+    for section in portfolio.content.iter().flatten() {
+        let title = section.title.as_deref().unwrap_or_default();
+        let (Some(kind), ContentValue::Items(items)) = (Kind::from_title(title), &section.content) else {
+            continue;
+        };
+        let entries = resume.entry(kind.key()).or_insert_with(|| json!([]));
+        if let Value::Array(entries) = entries {
+            entries.extend(items.iter().map(|item| entry(kind, item)));
+        }
+    }
+
+    let skills = super::skills(portfolio, formatted);
+    if !skills.is_empty() {
+        let skills = skills.iter().map(|skill| json!({ "name": skill.name })).collect();
+        resume.insert("skills".to_string(), Value::Array(skills));
+    }
+
+    let json = serde_json::to_string_pretty(&resume).context("Could not convert the portfolio to JSON")?;
+    Ok(format!("{}\n", json))
+}
+
+/// Titles of the sections [`render`] leaves out for having no equivalent
+pub fn skipped(portfolio: &Portfolio) -> Vec<&str> {
+    portfolio
+        .content
+        .iter()
+        .flatten()
+        .filter(|section| {
+            let title = section.title.as_deref().unwrap_or_default();
+            !matches!((Kind::from_title(title), &section.content), (Some(_), ContentValue::Items(_)))
+        })
+        .map(|section| section.title.as_deref().unwrap_or_default())
+        .collect()
+}
+
+/// Name, contacts, summary and profiles
+fn basics(portfolio: &Portfolio, formatted: &FormattedPortfolio) -> Value {
+    let image = portfolio.about_profile_image.as_deref().map(|image| match portfolio.website {
+        Some(ref website) if !image.contains("://") => {
+            format!("{}/{}", absolute_url(website).trim_end_matches('/'), image.trim_start_matches('/'))
+        }
+        _ => image.to_string(),
+    });
+
+    let mut basics = object([
+        ("name", Some(formatted.name.clone())),
+        ("label", Some(formatted.title.clone())),
+        ("email", portfolio.email.clone()),
+        ("url", portfolio.website.as_deref().map(absolute_url)),
+        ("image", image),
+        ("summary", portfolio.about_content.as_deref().map(clean_markdown)),
+    ]);
+
+    let profiles: Vec<Value> = formatted
+        .social
+        .iter()
+        .filter(|social| social.platform != "Email" && social.platform != "Website")
+        .map(|social| json!({ "network": social.platform, "username": social.username, "url": absolute_url(&social.url) }))
+        .chain(formatted.links.iter().map(|link| json!({ "network": link.title, "url": absolute_url(&link.url) })))
+        .collect();
+    if let Value::Object(ref mut basics) = basics {
+        basics.insert("profiles".to_string(), Value::Array(profiles));
+    }
+    basics
+}
+
+/// Resume entry for a content item
+fn entry(kind: Kind, item: &ContentItem) -> Value {
+    let title = item.title.clone();
+    let sub_title = item.sub_title.as_deref().map(|text| text.trim().to_string());
+    let url = item.url.as_deref().or(item.link.as_deref()).map(absolute_url);
+    let (start, end) = item.caption.as_deref().map(date_range).unwrap_or_default();
+    let summary = item
+        .description
+        .as_deref()
+        .or(item.quote.as_deref())
+        .map(clean_markdown);
+
+    match kind {
+        Kind::Work => object([
+            ("name", title),
+            ("position", sub_title),
+            ("url", url),
+            ("startDate", start),
+            ("endDate", end),
+            ("summary", summary),
+        ]),
+        Kind::Volunteer => object([
+            ("organization", title),
+            ("position", sub_title),
+            ("url", url),
+            ("startDate", start),
+            ("endDate", end),
+            ("summary", summary),
+        ]),
+        Kind::Education => object([
+            ("institution", title),
+            ("area", sub_title),
+            ("url", url),
+            ("startDate", start),
+            ("endDate", end),
+        ]),
+        Kind::Awards => object([("title", title), ("awarder", sub_title), ("date", start), ("summary", summary)]),
+        Kind::Certificates => object([("name", title), ("issuer", sub_title), ("date", start), ("url", url)]),
+        Kind::Publications => object([
+            ("name", title),
+            ("publisher", sub_title),
+            ("releaseDate", start),
+            ("url", url),
+            ("summary", summary),
+        ]),
+        Kind::Projects => object([
+            ("name", title),
+            ("description", summary),
+            ("url", url),
+            ("startDate", start),
+            ("endDate", end),
+        ]),
+        Kind::References => {
+            let name = [item.title.as_deref(), item.sub_title.as_deref()]
+                .into_iter()
+                .flatten()
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let reference = item.quote.as_deref().or(item.description.as_deref()).map(clean_markdown);
+            object([("name", Some(name)), ("reference", reference)])
+        }
+        Kind::Interests => object([("name", title)]),
+    }
+}
+
+/// JSON object of the fields that have a value
+fn object<const N: usize>(fields: [(&str, Option<String>); N]) -> Value {
+    let map = fields
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.to_string(), Value::String(value?))))
+        .collect();
+    Value::Object(map)
+}
+
+/// Start and end dates from a caption like `Jan 2020 - Mar 2022` or `2021 - Present`
+///
+/// Dates become `YYYY` or `YYYY-MM`. An open end like "Present" has no end date.
+fn date_range(caption: &str) -> (Option<String>, Option<String>) {
+    // A plain hyphen only separates when spaced, or between two years, since
+    // it is also part of ISO dates
+    let separator = Regex::new(r"\s*[–—]\s*|\s+-\s*|\s*-\s+|\s+to\s+|^\d{4}(-)\d{4}$").unwrap();
+    let caption = caption.trim();
+    let mut parts = match separator.captures(caption) {
+        Some(caps) => {
+            let split = caps.get(1).unwrap_or_else(|| caps.get(0).unwrap());
+            [&caption[..split.start()], &caption[split.end()..]].into_iter()
+        }
+        None => [caption, ""].into_iter(),
+    };
+    let start = parts.next().and_then(date);
+    let end = parts.next().and_then(date);
+    (start, end)
+}
+
+/// A single date in ISO 8601 form, if it can be read
+fn date(text: &str) -> Option<String> {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+    let text = text.trim().to_lowercase();
+    let iso = Regex::new(r"^(\d{4})(?:-(\d{2}))?(?:-(\d{2}))?$").unwrap();
+    if iso.is_match(&text) {
+        return Some(text);
+    }
+    let numeric = Regex::new(r"^(\d{1,2})/(\d{4})$").unwrap();
+    if let Some(caps) = numeric.captures(&text) {
+        return Some(format!("{}-{:0>2}", &caps[2], &caps[1]));
+    }
+    let named = Regex::new(r"^([a-z]+)\.?\s+(\d{4})$").unwrap();
+    if let Some(caps) = named.captures(&text) {
+        let month = MONTHS.iter().position(|month| caps[1].starts_with(month))?;
+        return Some(format!("{}-{:02}", &caps[2], month + 1));
+    }
+    None
+}
+
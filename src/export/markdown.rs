@@ -0,0 +1,171 @@
+//! Markdown export
+//!
+//! Writes a README-style document: a header with name, title and contacts,
+//! the about text and every content section. Descriptions are kept as the
+//! Markdown they were written in, so their links and emphasis survive.
+
+use regex::Regex;
+use std::fmt::Write;
+
+use super::absolute_url;
+use crate::data::models::{ContentItem, ContentSection, ContentValue, Portfolio};
+use crate::processor::formatter::FormattedPortfolio;
+
+/// Render the portfolio as Markdown
+pub fn render(portfolio: &Portfolio, formatted: &FormattedPortfolio) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", formatted.name);
+    let _ = writeln!(out, "**{}**\n", formatted.title);
+
+    let contacts: Vec<String> = formatted
+        .social
+        .iter()
+        .map(|social| {
+            let label = if social.platform == "Email" { &social.username } else { &social.platform };
+            format!("[{}]({})", label, absolute_url(&social.url))
+        })
+        .chain(formatted.links.iter().map(|link| format!("[{}]({})", link.title, absolute_url(&link.url))))
+        .collect();
+    if !contacts.is_empty() {
+        let _ = writeln!(out, "{}\n", contacts.join(" · "));
+    }
+
+    if let Some(ref about) = portfolio.about_content {
+        let _ = writeln!(out, "## About\n");
+        let _ = writeln!(out, "{}\n", clean_markdown(about));
+    }
+
+    for section in portfolio.content.iter().flatten() {
+        write_section(&mut out, section);
+    }
+    format!("{}\n", out.trim_end())
+}
+
+/// Write a section according to its layout
+fn write_section(out: &mut String, section: &ContentSection) {
+    if let Some(ref title) = section.title {
+        let _ = writeln!(out, "## {}\n", title);
+    }
+    match section.content {
+        ContentValue::Text(ref text) => {
+            let _ = writeln!(out, "{}\n", clean_markdown(text));
+        }
+        ContentValue::Items(ref items) => {
+            for item in items {
+                if is_quote(item) {
+                    write_quote(out, item);
+                } else {
+                    write_item(out, item);
+                }
+            }
+        }
+        ContentValue::Empty => {}
+    }
+}
+
+/// Whether an item is only a quote with its author, like a testimonial
+fn is_quote(item: &ContentItem) -> bool {
+    item.quote.is_some() && item.description.is_none() && item.caption.is_none()
+}
+
+/// Write a testimonial as a block quote with attribution
+fn write_quote(out: &mut String, item: &ContentItem) {
+    for line in item.quote.as_deref().unwrap_or_default().trim().lines() {
+        let _ = writeln!(out, "> {}", line);
+    }
+    let author = [item.title.as_deref(), item.sub_title.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if !author.is_empty() {
+        let _ = writeln!(out, ">\n> — {}", author);
+    }
+    let _ = writeln!(out);
+}
+
+/// Write a list or timeline entry
+///
+/// The subtitle and caption (usually dates) go on one line under the title,
+/// which links to the item's URL if it has one.
+fn write_item(out: &mut String, item: &ContentItem) {
+    let url = item.url.as_deref().or(item.link.as_deref()).map(absolute_url);
+    if let Some(ref title) = item.title {
+        match url {
+            Some(ref url) => {
+                let _ = writeln!(out, "### [{}]({})\n", title, url);
+            }
+            None => {
+                let _ = writeln!(out, "### {}\n", title);
+            }
+        }
+    }
+
+    let meta: Vec<String> = [item.sub_title.as_deref().map(|text| format!("*{}*", text.trim())), item.caption.clone()]
+        .into_iter()
+        .flatten()
+        .collect();
+    if !meta.is_empty() {
+        let _ = writeln!(out, "{}\n", meta.join(" · "));
+    }
+    if let Some(ref quote) = item.quote {
+        for line in quote.trim().lines() {
+            let _ = writeln!(out, "> {}", line);
+        }
+        let _ = writeln!(out);
+    }
+    if let Some(ref description) = item.description {
+        let _ = writeln!(out, "{}\n", clean_markdown(description));
+    }
+
+    let links: Vec<String> = item
+        .additional_links
+        .iter()
+        .flatten()
+        .filter_map(|link| {
+            let url = link.url.as_deref()?;
+            Some(format!("[{}]({})", link.title.as_deref().unwrap_or(url), absolute_url(url)))
+        })
+        .collect();
+    if !links.is_empty() {
+        let _ = writeln!(out, "{}\n", links.join(" · "));
+    }
+}
+
+/// Turn `<mark>` highlights into bold text and drop images with site-relative
+/// paths, which do not resolve outside the Jekyll site
+pub(super) fn clean_markdown(text: &str) -> String {
+    let text = text.replace("<mark>", "**").replace("</mark>", "**");
+    let images = Regex::new(r#"!\[[^\]]*\]\(/[^)]*\)\n?"#).unwrap();
+    images.replace_all(&text, "").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::parser::Parser;
+    use crate::processor::Formatter;
+
+    fn export(yaml: &str) -> String {
+        let portfolio = Parser::new().parse(yaml).unwrap();
+        render(&portfolio, &Formatter::new().format(&portfolio))
+    }
+
+    #[test]
+    fn links_are_absolute() {
+        let markdown = export(
+            "name: Test\nwebsite: example.com\nadditional_links:\n  - title: Blog\n    url: blog.example.com\n\
+             content:\n  - title: Projects\n    content:\n      - title: Thing\n        url: thing.example.com\n",
+        );
+        assert!(markdown.contains("[Website](https://example.com)"));
+        assert!(markdown.contains("[Blog](https://blog.example.com)"));
+        assert!(markdown.contains("### [Thing](https://thing.example.com)"));
+    }
+
+    #[test]
+    fn highlights_become_bold_and_site_images_are_dropped() {
+        let markdown = export("name: Test\nabout_content: \"I write <mark>Rust</mark>.\\n![me](/assets/me.png)\"\n");
+        assert!(markdown.contains("I write **Rust**."));
+        assert!(!markdown.contains("/assets/me.png"));
+    }
+}
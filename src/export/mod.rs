@@ -0,0 +1,128 @@
+//! Export to other document formats
+//!
+//! Each format lives in its own module and renders from the parsed
+//! [`Portfolio`](crate::data::Portfolio) together with its formatted form.
+
+pub mod gemtext;
+pub mod html;
+pub mod json;
+pub mod jsonresume;
+pub mod markdown;
+pub mod pdf;
+pub mod vcard;
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::config::args::{ExportArgs, ExportFormat};
+use crate::config::{Args, Settings};
+use crate::data::{self, Portfolio};
+use crate::processor::formatter::{FormattedPortfolio, FormattedSkill};
+use crate::processor::Formatter;
+
+/// Load the portfolio and write it in the requested format
+pub async fn export(args: &Args, export: &ExportArgs) -> Result<()> {
+    let mut portfolio = data::load(&args.config_path).await?;
+    if !export.sections.is_empty() {
+        select_sections(&mut portfolio, &export.sections)?;
+    }
+    let options = pdf::PdfOptions {
+        two_columns: export.columns == 2,
+        section_breaks: export.section_breaks,
+    };
+    let output = render(&portfolio, export.format, options)?;
+    if export.format == ExportFormat::JsonResume {
+        let skipped = jsonresume::skipped(&portfolio);
+        if !skipped.is_empty() {
+            eprintln!("Left out sections with no JSON Resume equivalent: {}", skipped.join(", "));
+        }
+    }
+
+    match export.output {
+        Some(ref path) => fs::write(path, output).with_context(|| format!("Could not write {}", path))?,
+        None => io::stdout().write_all(&output)?,
+    }
+    Ok(())
+}
+
+/// Render a loaded portfolio in a format
+///
+/// `options` only apply to PDF.
+pub fn render(portfolio: &Portfolio, format: ExportFormat, options: pdf::PdfOptions) -> Result<Vec<u8>> {
+    let formatted = Formatter::new().format(portfolio);
+    Ok(match format {
+        ExportFormat::Markdown => markdown::render(portfolio, &formatted).into_bytes(),
+        ExportFormat::Html => {
+            let template_dir = Settings::config_dir().map(|dir| dir.join("templates"));
+            html::render(portfolio, &formatted, template_dir.as_deref())?.into_bytes()
+        }
+        ExportFormat::Json => json::render(portfolio)?.into_bytes(),
+        ExportFormat::JsonResume => jsonresume::render(portfolio, &formatted)?.into_bytes(),
+        ExportFormat::Vcard => vcard::render(portfolio, &formatted).into_bytes(),
+        ExportFormat::Gemtext => gemtext::render(portfolio, &formatted).into_bytes(),
+        ExportFormat::Pdf => pdf::render(portfolio, &formatted, options)?,
+    })
+}
+
+/// Format a file name asks for by its extension
+///
+/// `.resume.json` picks JSON Resume over plain JSON.
+pub fn format_for_path(path: &str) -> Option<ExportFormat> {
+    let name = Path::new(path).file_name()?.to_str()?.to_lowercase();
+    if name.ends_with(".resume.json") {
+        return Some(ExportFormat::JsonResume);
+    }
+    let format = match name.rsplit_once('.')?.1 {
+        "md" | "markdown" => ExportFormat::Markdown,
+        "html" | "htm" => ExportFormat::Html,
+        "pdf" => ExportFormat::Pdf,
+        "json" => ExportFormat::Json,
+        "vcf" => ExportFormat::Vcard,
+        "gmi" => ExportFormat::Gemtext,
+        _ => return None,
+    };
+    Some(format)
+}
+
+/// Keep only the named sections, in the order they were given
+///
+/// Names match titles case-insensitively, exactly or else by prefix.
+fn select_sections(portfolio: &mut Portfolio, names: &[String]) -> Result<()> {
+    let mut sections = portfolio.content.take().unwrap_or_default();
+    let titles: Vec<String> = sections.iter().filter_map(|section| section.title.clone()).collect();
+    let mut selected = Vec::new();
+    for name in names {
+        let query = name.trim().to_lowercase();
+        let title = |index: &usize| sections[*index].title.as_deref().unwrap_or_default().to_lowercase();
+        let index = (0..sections.len())
+            .find(|i| title(i) == query)
+            .or_else(|| (0..sections.len()).find(|i| title(i).starts_with(&query)));
+        match index {
+            Some(index) => selected.push(sections.remove(index)),
+            None => bail!("Unknown section '{}', expected one of: {}", name.trim(), titles.join(", ")),
+        }
+    }
+    portfolio.content = Some(selected);
+    Ok(())
+}
+
+/// Add a scheme to links written without one, like `github.com/user`
+pub fn absolute_url(url: &str) -> String {
+    if url.contains("://") || url.starts_with("mailto:") || url.starts_with('/') {
+        url.to_string()
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+/// Skills highlighted in the about text
+///
+/// Empty when there are none, instead of the formatter's placeholder skills.
+fn skills<'a>(portfolio: &Portfolio, formatted: &'a FormattedPortfolio) -> &'a [FormattedSkill] {
+    match portfolio.about_content {
+        Some(ref about) if about.contains("<mark>") => &formatted.skills,
+        _ => &[],
+    }
+}
@@ -0,0 +1,636 @@
+//! PDF export
+//!
+//! Lays out an A4 resume without a browser: text is measured with the
+//! embedded DejaVu Sans font, wrapped, and placed page by page. Items are
+//! never split across pages and section headings stay with their first item.
+//! In the two-column layout both columns continue onto the following pages.
+//! Links become clickable annotations.
+
+use anyhow::{anyhow, Context, Result};
+use printpdf::{
+    Actions, BorderArray, Color, HighlightingMode, IndirectFontRef, Line, LinkAnnotation, Mm, PdfDocument,
+    PdfDocumentReference, PdfLayerReference, Point, Rect, Rgb,
+};
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use std::cell::RefCell;
+use ttf_parser::Face;
+
+use super::absolute_url;
+use crate::data::models::{ContentItem, ContentValue, Portfolio};
+use crate::processor::formatter::FormattedPortfolio;
+
+/// Regular weight of the embedded font
+const REGULAR_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+
+/// Bold weight of the embedded font
+const BOLD_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf");
+
+/// A4 page size in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+
+/// Page margin in millimetres
+const MARGIN: f32 = 18.0;
+
+/// Width of the contact column in the two-column layout
+const SIDEBAR_WIDTH: f32 = 52.0;
+
+/// Space between the columns
+const GUTTER: f32 = 8.0;
+
+/// Millimetres per typographic point
+const MM_PER_PT: f32 = 0.3528;
+
+/// Line height as a multiple of the font size
+const LINE_HEIGHT: f32 = 1.35;
+
+/// Text colors
+const TEXT: (f32, f32, f32) = (0.12, 0.14, 0.16);
+const MUTED: (f32, f32, f32) = (0.40, 0.43, 0.46);
+const ACCENT: (f32, f32, f32) = (0.04, 0.35, 0.75);
+
+/// Layout options
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfOptions {
+    /// Put contacts and skills in a column beside the content
+    pub two_columns: bool,
+    /// Start every section on a new page
+    pub section_breaks: bool,
+}
+
+/// Font weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Weight {
+    Regular,
+    Bold,
+}
+
+/// Size, weight and color of a run of text
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TextStyle {
+    size: f32,
+    weight: Weight,
+    color: (f32, f32, f32),
+}
+
+impl TextStyle {
+    const fn new(size: f32, weight: Weight, color: (f32, f32, f32)) -> Self {
+        Self { size, weight, color }
+    }
+
+    /// Height of a line in this style, in millimetres
+    fn line_height(&self) -> f32 {
+        self.size * LINE_HEIGHT * MM_PER_PT
+    }
+}
+
+const NAME: TextStyle = TextStyle::new(22.0, Weight::Bold, TEXT);
+const TITLE: TextStyle = TextStyle::new(12.0, Weight::Regular, MUTED);
+const HEADING: TextStyle = TextStyle::new(13.0, Weight::Bold, ACCENT);
+const ITEM_TITLE: TextStyle = TextStyle::new(11.0, Weight::Bold, TEXT);
+const META: TextStyle = TextStyle::new(9.5, Weight::Regular, MUTED);
+const BODY: TextStyle = TextStyle::new(10.0, Weight::Regular, TEXT);
+const STRONG: TextStyle = TextStyle::new(10.0, Weight::Bold, TEXT);
+const LINK: TextStyle = TextStyle::new(10.0, Weight::Regular, ACCENT);
+const SMALL_LINK: TextStyle = TextStyle::new(9.0, Weight::Regular, ACCENT);
+
+/// Smallest unit of wrapping: a word with its style and link
+#[derive(Debug, Clone)]
+struct Word {
+    text: String,
+    style: TextStyle,
+    link: Option<String>,
+    /// Separated from the previous word by a space
+    space_before: bool,
+}
+
+/// A laid out line of the document
+#[derive(Debug, Clone)]
+enum Row {
+    /// Words that fit on one line
+    Text(Vec<Word>),
+    /// Horizontal rule across the column
+    Rule,
+    /// Vertical space in millimetres
+    Space(f32),
+}
+
+impl Row {
+    /// Height of the row in millimetres
+    fn height(&self) -> f32 {
+        match self {
+            Row::Text(words) => words.iter().map(|word| word.style.line_height()).fold(0.0, f32::max),
+            Row::Rule => 2.0,
+            Row::Space(height) => *height,
+        }
+    }
+}
+
+/// Rows kept together on one page where possible
+#[derive(Debug, Clone, Default)]
+struct Block {
+    rows: Vec<Row>,
+    /// Start the block on a new page
+    page_break: bool,
+    /// Keep the block on the same page as the next one
+    keep_with_next: bool,
+}
+
+impl Block {
+    fn height(&self) -> f32 {
+        self.rows.iter().map(Row::height).sum()
+    }
+}
+
+/// Embedded fonts with their metrics
+struct Fonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    regular_face: Face<'static>,
+    bold_face: Face<'static>,
+}
+
+impl Fonts {
+    fn load(doc: &PdfDocumentReference) -> Result<Self> {
+        Ok(Self {
+            regular: doc.add_external_font(REGULAR_FONT).context("Could not embed the regular font")?,
+            bold: doc.add_external_font(BOLD_FONT).context("Could not embed the bold font")?,
+            regular_face: Face::parse(REGULAR_FONT, 0).map_err(|err| anyhow!("Invalid regular font: {}", err))?,
+            bold_face: Face::parse(BOLD_FONT, 0).map_err(|err| anyhow!("Invalid bold font: {}", err))?,
+        })
+    }
+
+    fn font(&self, weight: Weight) -> &IndirectFontRef {
+        match weight {
+            Weight::Regular => &self.regular,
+            Weight::Bold => &self.bold,
+        }
+    }
+
+    /// Width of `text` in millimetres
+    fn width(&self, text: &str, style: TextStyle) -> f32 {
+        let face = match style.weight {
+            Weight::Regular => &self.regular_face,
+            Weight::Bold => &self.bold_face,
+        };
+        let units: u32 = text
+            .chars()
+            .map(|c| face.glyph_index(c).and_then(|glyph| face.glyph_hor_advance(glyph)).unwrap_or(0) as u32)
+            .sum();
+        units as f32 / face.units_per_em() as f32 * style.size * MM_PER_PT
+    }
+}
+
+/// Render the portfolio as a PDF document
+pub fn render(portfolio: &Portfolio, formatted: &FormattedPortfolio, options: PdfOptions) -> Result<Vec<u8>> {
+    let (doc, page, layer) = PdfDocument::new(
+        format!("{} - {}", formatted.name, formatted.title),
+        Mm(PAGE_WIDTH),
+        Mm(PAGE_HEIGHT),
+        "Content",
+    );
+    let fonts = Fonts::load(&doc)?;
+    let pages = RefCell::new(vec![doc.get_page(page).get_layer(layer)]);
+
+    let full_width = PAGE_WIDTH - 2.0 * MARGIN;
+    let (main_x, main_width) = if options.two_columns {
+        (MARGIN + SIDEBAR_WIDTH + GUTTER, full_width - SIDEBAR_WIDTH - GUTTER)
+    } else {
+        (MARGIN, full_width)
+    };
+
+    // Header across the full width of the first page
+    let mut header = Block::default();
+    header.rows.extend(wrap(words(&formatted.name, NAME, None), full_width, &fonts));
+    header.rows.extend(wrap(words(&formatted.title, TITLE, None), full_width, &fonts));
+    header.rows.push(Row::Space(3.0));
+    if !options.two_columns {
+        header.rows.extend(wrap(contact_words(formatted, LINK, " · "), full_width, &fonts));
+        header.rows.push(Row::Space(2.0));
+    }
+    let mut writer = Writer::new(&doc, &fonts, &pages, MARGIN, full_width);
+    writer.place(&header, None);
+    let content_top = writer.y;
+
+    // Contacts and skills beside the content
+    if options.two_columns {
+        let mut sidebar = Writer::new(&doc, &fonts, &pages, MARGIN, SIDEBAR_WIDTH);
+        sidebar.y = content_top;
+        for block in sidebar_blocks(portfolio, formatted, &fonts) {
+            sidebar.place(&block, None);
+        }
+    }
+
+    let blocks = content_blocks(portfolio, main_width, options, &fonts);
+    writer.x = main_x;
+    writer.width = main_width;
+    for (i, block) in blocks.iter().enumerate() {
+        writer.place(block, blocks.get(i + 1));
+    }
+
+    doc.save_to_bytes().context("Could not write the PDF")
+}
+
+/// Contact and skill lists for the two-column layout
+fn sidebar_blocks(portfolio: &Portfolio, formatted: &FormattedPortfolio, fonts: &Fonts) -> Vec<Block> {
+    let mut contacts = Block::default();
+    contacts.rows.extend(heading("Contact", SIDEBAR_WIDTH, fonts));
+    for (label, url) in contacts_of(formatted) {
+        contacts.rows.extend(wrap(words(&label, SMALL_LINK, Some(&url)), SIDEBAR_WIDTH, fonts));
+    }
+
+    let mut skills = Block::default();
+    if !super::skills(portfolio, formatted).is_empty() {
+        skills.rows.push(Row::Space(4.0));
+        skills.rows.extend(heading("Skills", SIDEBAR_WIDTH, fonts));
+        for skill in super::skills(portfolio, formatted) {
+            skills.rows.extend(wrap(words(&skill.name, META, None), SIDEBAR_WIDTH, fonts));
+        }
+    }
+    vec![contacts, skills]
+}
+
+/// About text and every content section
+fn content_blocks(portfolio: &Portfolio, width: f32, options: PdfOptions, fonts: &Fonts) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    if let Some(ref about) = portfolio.about_content {
+        blocks.push(Block {
+            rows: heading("About", width, fonts),
+            keep_with_next: true,
+            ..Block::default()
+        });
+        blocks.push(Block {
+            rows: markdown_rows(about, width, fonts),
+            ..Block::default()
+        });
+    }
+
+    for (i, section) in portfolio.content.iter().flatten().enumerate() {
+        let mut title = Block {
+            page_break: options.section_breaks && (i > 0 || portfolio.about_content.is_some()),
+            keep_with_next: true,
+            ..Block::default()
+        };
+        if !blocks.is_empty() && !title.page_break {
+            title.rows.push(Row::Space(4.0));
+        }
+        title.rows.extend(heading(section.title.as_deref().unwrap_or_default(), width, fonts));
+        blocks.push(title);
+
+        match section.content {
+            ContentValue::Text(ref text) => blocks.push(Block {
+                rows: markdown_rows(text, width, fonts),
+                ..Block::default()
+            }),
+            ContentValue::Items(ref items) => blocks.extend(items.iter().map(|item| item_block(item, width, fonts))),
+            ContentValue::Empty => {}
+        }
+    }
+    blocks
+}
+
+/// A content item: title, subtitle and dates, quote, description and links
+fn item_block(item: &ContentItem, width: f32, fonts: &Fonts) -> Block {
+    let mut block = Block::default();
+    let url = item.url.as_deref().or(item.link.as_deref()).map(absolute_url);
+    if let Some(ref title) = item.title {
+        let style = if url.is_some() { TextStyle { color: ACCENT, ..ITEM_TITLE } } else { ITEM_TITLE };
+        block.rows.extend(wrap(words(title, style, url.as_deref()), width, fonts));
+    }
+
+    let meta: Vec<&str> = [item.sub_title.as_deref(), item.caption.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .collect();
+    if !meta.is_empty() {
+        block.rows.extend(wrap(words(&meta.join(" · "), META, None), width, fonts));
+    }
+    block.rows.push(Row::Space(1.0));
+
+    if let Some(ref quote) = item.quote {
+        let quote = format!("“{}”", quote.trim());
+        block.rows.extend(wrap(words(&quote, TextStyle { color: MUTED, ..BODY }, None), width, fonts));
+        block.rows.push(Row::Space(1.0));
+    }
+    if let Some(ref description) = item.description {
+        block.rows.extend(markdown_rows(description, width, fonts));
+    }
+
+    let mut links = Vec::new();
+    for link in item.additional_links.iter().flatten() {
+        let Some(ref url) = link.url else {
+            continue;
+        };
+        if !links.is_empty() {
+            links.extend(words("·", META, None));
+        }
+        links.extend(words(link.title.as_deref().unwrap_or(url), SMALL_LINK, Some(&absolute_url(url))));
+    }
+    if !links.is_empty() {
+        block.rows.extend(wrap(links, width, fonts));
+    }
+    block.rows.push(Row::Space(3.0));
+    block
+}
+
+/// Section heading with a rule under it
+fn heading(text: &str, width: f32, fonts: &Fonts) -> Vec<Row> {
+    let mut rows = wrap(words(text, HEADING, None), width, fonts);
+    rows.push(Row::Rule);
+    rows.push(Row::Space(1.0));
+    rows
+}
+
+/// Contacts as (label, URL) pairs
+fn contacts_of(formatted: &FormattedPortfolio) -> Vec<(String, String)> {
+    formatted
+        .social
+        .iter()
+        .map(|social| {
+            let label = if social.platform == "Email" { &social.username } else { &social.platform };
+            (label.clone(), absolute_url(&social.url))
+        })
+        .chain(formatted.links.iter().map(|link| (link.title.clone(), absolute_url(&link.url))))
+        .collect()
+}
+
+/// Contacts on one wrapping line, separated by `separator`
+fn contact_words(formatted: &FormattedPortfolio, style: TextStyle, separator: &str) -> Vec<Word> {
+    let mut result = Vec::new();
+    for (label, url) in contacts_of(formatted) {
+        if !result.is_empty() {
+            result.extend(words(separator, META, None));
+        }
+        result.extend(words(&label, style, Some(&url)));
+    }
+    result
+}
+
+/// Split text into words sharing a style and link
+fn words(text: &str, style: TextStyle, link: Option<&str>) -> Vec<Word> {
+    text.split_whitespace()
+        .map(|word| Word {
+            text: word.to_string(),
+            style,
+            link: link.map(str::to_string),
+            space_before: true,
+        })
+        .collect()
+}
+
+/// Render Markdown to rows, keeping bold text and links and leaving out images
+fn markdown_rows(text: &str, width: f32, fonts: &Fonts) -> Vec<Row> {
+    let text = text.replace("<mark>", "**").replace("</mark>", "**");
+    let mut rows = Vec::new();
+    let mut paragraph: Vec<Word> = Vec::new();
+    let mut link: Option<String> = None;
+    let mut strong = 0;
+    let mut in_image = false;
+    // A chunk that does not start with a space continues the previous word
+    let mut space_pending = true;
+
+    for event in Parser::new(&text) {
+        match event {
+            Event::Start(Tag::Image { .. }) => in_image = true,
+            Event::End(TagEnd::Image) => in_image = false,
+            _ if in_image => {}
+            Event::Start(Tag::Strong) => strong += 1,
+            Event::End(TagEnd::Strong) => strong -= 1,
+            Event::Start(Tag::Link { dest_url, .. }) => link = Some(absolute_url(&dest_url)),
+            Event::End(TagEnd::Link) => link = None,
+            Event::Start(Tag::Item) => {
+                paragraph.extend(words("•", BODY, None));
+                space_pending = true;
+            }
+            Event::Text(chunk) | Event::Code(chunk) => {
+                let style = match (&link, strong > 0) {
+                    (Some(_), _) => LINK,
+                    (None, true) => STRONG,
+                    (None, false) => BODY,
+                };
+                let starts_with_space = chunk.starts_with(char::is_whitespace);
+                let mut chunk_words = words(&chunk, style, link.as_deref());
+                if let Some(first) = chunk_words.first_mut() {
+                    first.space_before = space_pending || starts_with_space;
+                }
+                space_pending = chunk.ends_with(char::is_whitespace);
+                paragraph.extend(chunk_words);
+            }
+            Event::SoftBreak => space_pending = true,
+            Event::HardBreak | Event::End(TagEnd::Paragraph | TagEnd::Item) => {
+                if !paragraph.is_empty() {
+                    rows.extend(wrap(std::mem::take(&mut paragraph), width, fonts));
+                    rows.push(Row::Space(1.5));
+                }
+                space_pending = true;
+            }
+            _ => {}
+        }
+    }
+    if !paragraph.is_empty() {
+        rows.extend(wrap(paragraph, width, fonts));
+    }
+    rows
+}
+
+/// Break words into rows no wider than `width`
+fn wrap(words: Vec<Word>, width: f32, fonts: &Fonts) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut row: Vec<Word> = Vec::new();
+    let mut used = 0.0;
+    for word in words.into_iter().flat_map(|word| split_wide(word, width, fonts)) {
+        let word_width = fonts.width(&word.text, word.style);
+        let space = if word.space_before && !row.is_empty() { fonts.width(" ", word.style) } else { 0.0 };
+        if !row.is_empty() && used + space + word_width > width {
+            rows.push(Row::Text(std::mem::take(&mut row)));
+            used = word_width;
+        } else {
+            used += space + word_width;
+        }
+        row.push(word);
+    }
+    if !row.is_empty() {
+        rows.push(Row::Text(row));
+    }
+    rows
+}
+
+/// Split a word wider than `width`, like a long URL, into pieces that fit
+fn split_wide(word: Word, width: f32, fonts: &Fonts) -> Vec<Word> {
+    if fonts.width(&word.text, word.style) <= width {
+        return vec![word];
+    }
+    let mut pieces = Vec::new();
+    let mut piece = String::new();
+    let mut used = 0.0;
+    for c in word.text.chars() {
+        let char_width = fonts.width(c.encode_utf8(&mut [0; 4]), word.style);
+        if !piece.is_empty() && used + char_width > width {
+            pieces.push(std::mem::take(&mut piece));
+            used = 0.0;
+        }
+        piece.push(c);
+        used += char_width;
+    }
+    pieces.push(piece);
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| Word {
+            text,
+            space_before: i == 0 && word.space_before,
+            ..word.clone()
+        })
+        .collect()
+}
+
+/// Places rows on pages, top to bottom within a column
+struct Writer<'a> {
+    doc: &'a PdfDocumentReference,
+    fonts: &'a Fonts,
+    /// Layer of every page so far, shared by the writers of both columns
+    pages: &'a RefCell<Vec<PdfLayerReference>>,
+    /// Index of the page the column is on
+    page: usize,
+    layer: PdfLayerReference,
+    /// Left edge of the column
+    x: f32,
+    /// Width of the column
+    width: f32,
+    /// Top of the next row, measured from the bottom of the page
+    y: f32,
+}
+
+impl<'a> Writer<'a> {
+    /// Writer for a column starting at the top of the first page
+    fn new(
+        doc: &'a PdfDocumentReference,
+        fonts: &'a Fonts,
+        pages: &'a RefCell<Vec<PdfLayerReference>>,
+        x: f32,
+        width: f32,
+    ) -> Self {
+        let layer = pages.borrow()[0].clone();
+        Self {
+            doc,
+            fonts,
+            pages,
+            page: 0,
+            layer,
+            x,
+            width,
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    /// Continue at the top of the next page, adding it if the other column
+    /// has not already
+    fn new_page(&mut self) {
+        self.page += 1;
+        let mut pages = self.pages.borrow_mut();
+        if self.page == pages.len() {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+            pages.push(self.doc.get_page(page).get_layer(layer));
+        }
+        self.layer = pages[self.page].clone();
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Space left in the column on this page
+    fn remaining(&self) -> f32 {
+        self.y - MARGIN
+    }
+
+    /// Place a block, moving it to the next page if it would be split
+    ///
+    /// Blocks taller than a page are split row by row. A block that keeps
+    /// with the next one moves if the two do not fit together.
+    fn place(&mut self, block: &Block, next: Option<&Block>) {
+        let page_height = PAGE_HEIGHT - 2.0 * MARGIN;
+        let mut needed = block.height();
+        if block.keep_with_next
+            && let Some(next) = next
+        {
+            needed += next.height();
+        }
+        let at_top = self.y >= PAGE_HEIGHT - MARGIN;
+        if !at_top && (block.page_break || (needed <= page_height && needed > self.remaining())) {
+            self.new_page();
+        }
+
+        for row in &block.rows {
+            let height = row.height();
+            if height > self.remaining() {
+                self.new_page();
+                if matches!(row, Row::Space(_)) {
+                    continue;
+                }
+            }
+            self.draw(row);
+            self.y -= height;
+        }
+    }
+
+    /// Draw a row at the current position
+    fn draw(&self, row: &Row) {
+        match row {
+            Row::Text(words) => {
+                let height = row.height();
+                let size = words.iter().map(|word| word.style.size).fold(0.0, f32::max);
+                let baseline = self.y - size * MM_PER_PT;
+                let mut x = self.x;
+                let mut link_start: Option<(f32, &str)> = None;
+                for (i, word) in words.iter().enumerate() {
+                    if word.space_before && i > 0 {
+                        x += self.fonts.width(" ", word.style);
+                    }
+                    // Close the running link when the target changes
+                    if let Some((start, url)) = link_start
+                        && word.link.as_deref() != Some(url)
+                    {
+                        self.link(start, x, height, url);
+                        link_start = None;
+                    }
+                    if let (None, Some(url)) = (link_start, word.link.as_deref()) {
+                        link_start = Some((x, url));
+                    }
+
+                    let (r, g, b) = word.style.color;
+                    self.layer.set_fill_color(Color::Rgb(Rgb::new(r, g, b, None)));
+                    self.layer.use_text(&word.text, word.style.size, Mm(x), Mm(baseline), self.fonts.font(word.style.weight));
+                    x += self.fonts.width(&word.text, word.style);
+                }
+                if let Some((start, url)) = link_start {
+                    self.link(start, x, height, url);
+                }
+            }
+            Row::Rule => {
+                let y = self.y - 0.8;
+                let (r, g, b) = MUTED;
+                self.layer.set_outline_color(Color::Rgb(Rgb::new(r, g, b, None)));
+                self.layer.set_outline_thickness(0.5);
+                self.layer.add_line(Line {
+                    points: vec![
+                        (Point::new(Mm(self.x), Mm(y)), false),
+                        (Point::new(Mm(self.x + self.width), Mm(y)), false),
+                    ],
+                    is_closed: false,
+                });
+            }
+            Row::Space(_) => {}
+        }
+    }
+
+    /// Make the area from `start` to `end` on the current row a link to `url`
+    fn link(&self, start: f32, end: f32, height: f32, url: &str) {
+        let rect = Rect::new(Mm(start), Mm(self.y - height), Mm(end), Mm(self.y));
+        self.layer.add_link_annotation(LinkAnnotation::new(
+            rect,
+            Some(BorderArray::Solid([0.0, 0.0, 0.0])),
+            None,
+            Actions::uri(url.to_string()),
+            Some(HighlightingMode::Invert),
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+//! vCard export
+//!
+//! Writes a vCard 4.0 contact (RFC 6350) from the fields on the Home view:
+//! name, title, email, website and social profiles. Profiles use the
+//! `SOCIALPROFILE` property from RFC 9554.
+
+use super::absolute_url;
+use crate::data::models::Portfolio;
+use crate::processor::formatter::FormattedPortfolio;
+
+/// Longest line in octets before it is folded
+const LINE_LIMIT: usize = 75;
+
+/// Render the contact as a vCard
+pub fn render(portfolio: &Portfolio, formatted: &FormattedPortfolio) -> String {
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:4.0".to_string(),
+        format!("FN:{}", escape(&formatted.name)),
+    ];
+    if portfolio.title.is_some() {
+        lines.push(format!("TITLE:{}", escape(&formatted.title)));
+    }
+    if let Some(ref email) = portfolio.email {
+        lines.push(format!("EMAIL:{}", escape(email)));
+    }
+    // URL and SOCIALPROFILE hold URIs, which are written as they are rather
+    // than escaped like text
+    if let Some(ref website) = portfolio.website {
+        lines.push(format!("URL:{}", absolute_url(website.trim())));
+    }
+    for social in &formatted.social {
+        if social.platform == "Email" || social.platform == "Website" {
+            continue;
+        }
+        lines.push(format!("SOCIALPROFILE;SERVICE-TYPE={}:{}", social.platform, absolute_url(&social.url)));
+    }
+    lines.push("END:VCARD".to_string());
+
+    lines.iter().map(|line| fold(line)).collect::<Vec<_>>().join("")
+}
+
+/// Escape a text value; URI values are not escaped
+fn escape(text: &str) -> String {
+    text.trim()
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets and end it with CRLF
+///
+/// Continuation lines start with a space, and lines only break between
+/// characters so multi-byte characters stay intact.
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            length = 1;
+        }
+        out.push(c);
+        length += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::parser::Parser;
+    use crate::processor::Formatter;
+
+    fn export(yaml: &str) -> String {
+        let portfolio = Parser::new().parse(yaml).unwrap();
+        render(&portfolio, &Formatter::new().format(&portfolio))
+    }
+
+    #[test]
+    fn urls_are_absolute_and_not_escaped() {
+        let vcard = export("name: Test\nwebsite: example.com/a,b;c\ngithub_username: tester\n");
+        assert!(vcard.contains("\r\nURL:https://example.com/a,b;c\r\n"));
+        assert!(vcard.contains("\r\nSOCIALPROFILE;SERVICE-TYPE=GitHub:https://github.com/tester\r\n"));
+    }
+
+    #[test]
+    fn text_is_escaped_and_long_lines_folded() {
+        let vcard = export(&format!("name: Smith, Jane; Dr\ntitle: {}\n", "x".repeat(100)));
+        assert!(vcard.contains("\r\nFN:Smith\\, Jane\\; Dr\r\n"));
+        assert!(vcard.lines().all(|line| line.len() <= LINE_LIMIT));
+        assert!(vcard.contains("\r\n x"));
+    }
+}
@@ -1,29 +1,63 @@
 mod config;
 mod data;
+mod export;
 mod processor;
+mod serve;
 mod ui;
 
 use anyhow::Result;
 use clap::Parser;
-use config::args::Args;
+use config::args::{Args, Command};
+use config::Settings;
+use ui::color::ColorDepth;
+use ui::keymap::Keymap;
+use ui::theme::ThemeSet;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse command line arguments
-    let args = Args::parse();
+    let mut args = Args::parse();
     
-    // Check if the config path is a local file that exists
-    if !args.config_path.starts_with("http") && std::path::Path::new(&args.config_path).exists() {
-        println!("Using local config file: {}", args.config_path);
-    } else if args.config_path.starts_with("http") {
-        println!("Using remote config file: {}", args.config_path);
-    } else {
-        println!("Warning: Config file not found at: {}", args.config_path);
-        println!("Will attempt to use default GitHub config.");
+    // Check if the config path is a local file that exists, keeping printed
+    // output free of extra messages
+    if !args.inline && args.command.is_none() {
+        if !args.config_path.starts_with("http") && std::path::Path::new(&args.config_path).exists() {
+            println!("Using local config file: {}", args.config_path);
+        } else if args.config_path.starts_with("http") {
+            println!("Using remote config file: {}", args.config_path);
+        } else {
+            println!("Warning: Config file not found at: {}", args.config_path);
+            println!("Will attempt to use default GitHub config.");
+        }
+    }
+    
+    // Load settings and key bindings before touching the terminal
+    let settings = Settings::load(args.settings.as_deref())?;
+    if settings.mouse == Some(false) {
+        args.no_mouse = true;
+    }
+    let keymap = Keymap::from_settings(&settings.keymap, args.keymap.as_deref())?;
+    // Screenshots keep the full theme colors whatever the terminal supports,
+    // and servers reduce them for each remote terminal
+    let depth = match args.command {
+        Some(Command::Screenshot(_) | Command::Serve(_)) => ColorDepth::TrueColor,
+        _ => ColorDepth::detect(),
+    };
+    let themes = ThemeSet::load(Settings::config_dir().map(|dir| dir.join("themes")).as_deref(), depth)?;
+    
+    match args.command {
+        Some(Command::Print(ref print)) => return ui::print::print(&args, print, &themes).await,
+        Some(Command::Export(ref export)) => return export::export(&args, export).await,
+        Some(Command::Screenshot(ref shot)) => return ui::screenshot::screenshot(&args, shot, keymap, themes).await,
+        Some(Command::Serve(ref serve)) => return serve::serve(&args, serve, keymap, themes).await,
+        None => {}
+    }
+    if args.inline {
+        return ui::inline::print(&args, &themes).await;
     }
     
     // Initialize the application
-    let mut app = ui::app::App::new(args)?;
+    let mut app = ui::app::App::new(args, keymap, themes)?;
     
     // Run the application
     app.run().await?;
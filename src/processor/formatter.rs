@@ -1,5 +1,8 @@
-use crate::data::models::{Portfolio, ContentValue, ContentSection};
+use crate::data::models::{AdditionalLink, Portfolio, ContentValue, ContentSection};
 use regex::Regex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use textwrap::wrap;
 
 /// Formats data for display
@@ -199,6 +202,9 @@ impl Formatter {
             });
         }
         
+        // Extract additional links
+        let links = self.format_links(portfolio.additional_links.as_deref());
+        
         FormattedPortfolio {
             name,
             title,
@@ -207,9 +213,26 @@ impl Formatter {
             projects,
             skills,
             social,
+            links,
         }
     }
 
+    /// Format a list of additional links, skipping links without a URL
+    fn format_links(&self, links: Option<&[AdditionalLink]>) -> Vec<FormattedLink> {
+        links
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|link| {
+                let url = link.url.clone()?;
+                Some(FormattedLink {
+                    title: link.title.clone().unwrap_or_else(|| url.clone()),
+                    icon: link.icon.clone().unwrap_or_default(),
+                    url,
+                })
+            })
+            .collect()
+    }
+
     /// Format a content section
     fn format_content_section(&self, section: &ContentSection) -> FormattedContentSection {
         let title = section.title.clone().unwrap_or_default();
@@ -225,8 +248,10 @@ impl Formatter {
                     let processed_description = self.process_text(&description);
                     let quote = item.quote.clone().unwrap_or_default();
                     let processed_quote = self.process_text(&quote);
-                    let _url = item.url.clone().unwrap_or_default();
-                    let _link = item.link.clone().unwrap_or_default();
+                    let url = item.url.clone().unwrap_or_default();
+                    let link = item.link.clone().unwrap_or_default();
+                    let icon = item.icon.clone().unwrap_or_default();
+                    let links = self.format_links(item.additional_links.as_deref());
                     
                     FormattedContentItem {
                         title,
@@ -234,8 +259,10 @@ impl Formatter {
                         caption,
                         description: processed_description,
                         quote: processed_quote,
-                        _url,
-                        _link,
+                        icon,
+                        links,
+                        url,
+                        link,
                     }
                 }).collect()
             },
@@ -247,8 +274,10 @@ impl Formatter {
                     caption: String::new(),
                     description: processed_text,
                     quote: String::new(),
-                    _url: String::new(),
-                    _link: String::new(),
+                    icon: String::new(),
+                    links: Vec::new(),
+                    url: String::new(),
+                    link: String::new(),
                 }]
             },
             ContentValue::Empty => Vec::new(),
@@ -262,6 +291,34 @@ impl Formatter {
     }
 }
 
+/// Most widths kept in a [`TextCache`] before it starts over
+const TEXT_CACHE_LIMIT: usize = 32;
+
+/// Processed text remembered by source text and wrap width
+///
+/// Views ask for the same text at the same width on every frame, so the
+/// markdown cleanup and wrapping only run when either changes.
+#[derive(Debug, Default)]
+pub struct TextCache {
+    entries: HashMap<(u64, usize), String>,
+}
+
+impl TextCache {
+    /// Process `text` for `width` columns, reusing an earlier result
+    pub fn process_text(&mut self, text: &str, width: usize) -> &str {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let key = (hasher.finish(), width);
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= TEXT_CACHE_LIMIT {
+            self.entries.clear();
+        }
+        self.entries
+            .entry(key)
+            .or_insert_with(|| Formatter::with_max_width(width).process_text(text))
+    }
+}
+
 /// Formatted portfolio data for display
 #[derive(Debug, Clone)]
 pub struct FormattedPortfolio {
@@ -272,6 +329,7 @@ pub struct FormattedPortfolio {
     pub projects: Vec<FormattedProject>,
     pub skills: Vec<FormattedSkill>,
     pub social: Vec<FormattedSocial>,
+    pub links: Vec<FormattedLink>,
 }
 
 /// Formatted content section
@@ -290,9 +348,10 @@ pub struct FormattedContentItem {
     pub caption: String,
     pub description: String,
     pub quote: String,
-    // These fields are kept for future use but marked with underscores to avoid warnings
-    pub _url: String,
-    pub _link: String,
+    pub icon: String,
+    pub links: Vec<FormattedLink>,
+    pub url: String,
+    pub link: String,
 }
 
 /// Formatted project data for display
@@ -320,4 +379,12 @@ pub struct FormattedSocial {
     pub platform: String,
     pub url: String,
     pub username: String,
-}
\ No newline at end of file
+}
+
+/// Formatted additional link data for display
+#[derive(Debug, Clone)]
+pub struct FormattedLink {
+    pub title: String,
+    pub icon: String,
+    pub url: String,
+}
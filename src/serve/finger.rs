@@ -0,0 +1,98 @@
+//! Finger server
+//!
+//! Answers finger queries (RFC 1288) with the portfolio as plain text, the
+//! same as the `print` command. The portfolio belongs to one person, so any
+//! user name gets all of it; a section title or navigation index gets just
+//! that section, as in `finger projects@example.com`.
+
+use anyhow::Result;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use super::text::{self, Printer};
+use crate::config::args::FingerArgs;
+use crate::processor::formatter::FormattedPortfolio;
+use crate::ui::print;
+
+/// Answer finger queries until the server fails
+pub async fn serve(printer: Printer, finger: &FingerArgs) -> Result<()> {
+    let listener = text::listen(&finger.bind, finger.port).await?;
+    println!("Serving finger on {}:{}", finger.bind, finger.port);
+    run(listener, printer, finger.max_connections).await
+}
+
+/// Answer finger queries on a listener
+async fn run(listener: TcpListener, printer: Printer, max_connections: usize) -> Result<()> {
+    let printer = Arc::new(printer);
+    text::answer(listener, max_connections, move |query| {
+        let printer = printer.clone();
+        async move { respond(&printer, &query).await }
+    })
+    .await
+}
+
+/// Response to a query, with CRLF line endings
+///
+/// Load errors are logged rather than shown, as they can name local paths.
+async fn respond(printer: &Printer, query: &str) -> String {
+    // `/W` asks for a verbose answer, which is the only kind there is
+    let query = query.strip_prefix("/W").unwrap_or(query).trim();
+    let lines = if query.contains('@') {
+        vec!["finger: Forwarding queries to other hosts is not supported".to_string()]
+    } else {
+        match printer.load().await {
+            Ok(portfolio) => lookup(printer, &portfolio, query),
+            Err(err) => {
+                eprintln!("{:#}", err);
+                vec!["finger: portfolio unavailable".to_string()]
+            }
+        }
+    };
+    lines.iter().map(|line| format!("{}\r\n", line)).collect()
+}
+
+/// Lines answering a query: the section it names, or everything
+fn lookup(printer: &Printer, portfolio: &FormattedPortfolio, query: &str) -> Vec<String> {
+    let pages = match print::find_page(portfolio, query) {
+        Ok(page) if !query.is_empty() => vec![page],
+        _ => print::all_pages(portfolio),
+    };
+    printer.pages(portfolio, &pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serve::testing::{ask, local_listener, Config};
+
+    const YAML: &str = "name: Test Person\ntitle: Tester\ncontent:\n  - title: Projects\n    layout: list\n    content:\n      - title: Thing\n        description: Made a thing\n  - title: Talks\n    content: Gave a talk\n";
+
+    #[tokio::test]
+    async fn queries_are_answered_over_tcp() {
+        let config = Config::new("finger", YAML);
+        let (listener, addr) = local_listener().await;
+        tokio::spawn(run(listener, config.printer(), 4));
+
+        for query in [&b"pokey\r\n"[..], b"\r\n", b"/W pokey\r\n"] {
+            let response = ask(addr, query).await;
+            assert!(response.starts_with("Test Person"), "{:?}", response);
+            assert!(response.contains("Made a thing") && response.contains("Gave a talk"));
+            assert!(response.split_inclusive('\n').all(|line| line.ends_with("\r\n")));
+        }
+
+        let response = ask(addr, b"talks\r\n").await;
+        assert!(response.contains("Gave a talk") && !response.contains("Made a thing"));
+        assert_eq!(ask(addr, b"pokey@elsewhere\r\n").await, "finger: Forwarding queries to other hosts is not supported\r\n");
+    }
+
+    #[tokio::test]
+    async fn load_errors_are_not_shown() {
+        let config = Config::new("finger-missing", YAML);
+        let printer = config.printer();
+        drop(config);
+        let (listener, addr) = local_listener().await;
+        tokio::spawn(run(listener, printer, 4));
+
+        assert_eq!(ask(addr, b"\r\n").await, "finger: portfolio unavailable\r\n");
+    }
+}
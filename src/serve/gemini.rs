@@ -0,0 +1,214 @@
+//! Gemini server
+//!
+//! Serves the portfolio over the Gemini protocol as one gemtext page, the
+//! same as `export --format gemtext`. Gemini clients trust the first
+//! certificate a host presents, so a self-signed certificate for
+//! `--hostname` is generated on first start and reused after that. Requests
+//! for other hosts are refused.
+
+use anyhow::{anyhow, Context, Result};
+use rcgen::{CertificateParams, DnType, KeyPair};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::time;
+use tokio_rustls::rustls::crypto::ring::default_provider;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+use super::text::{self, Cache};
+use super::write_private;
+use crate::config::args::GeminiArgs;
+use crate::config::Settings;
+use crate::data;
+use crate::export::gemtext;
+use crate::processor::Formatter;
+
+/// Scheme of the URLs clients request
+const SCHEME: &str = "gemini://";
+
+/// Load the certificate and answer requests until the server fails
+pub async fn serve(source: String, gemini: &GeminiArgs) -> Result<()> {
+    let (certs, key) = match (&gemini.cert, &gemini.key) {
+        (Some(cert), Some(key)) => load_certificate(Path::new(cert), Path::new(key))?,
+        _ => {
+            let dir = Settings::config_dir()
+                .context("Could not find the config directory for the certificate, pass --cert and --key")?;
+            let cert = dir.join(format!("gemini_{}_cert.pem", gemini.hostname));
+            let key = dir.join(format!("gemini_{}_key.pem", gemini.hostname));
+            if !cert.exists() {
+                generate_certificate(&cert, &key, &gemini.hostname)?;
+            }
+            load_certificate(&cert, &key)?
+        }
+    };
+    let fingerprint = fingerprint(&certs[0]);
+
+    let config = ServerConfig::builder_with_provider(Arc::new(default_provider()))
+        .with_safe_default_protocol_versions()
+        .context("Could not set up TLS")?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("The certificate does not match the key")?;
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let listener = text::listen(&gemini.bind, gemini.port).await?;
+    println!(
+        "Serving Gemini on {}:{}, certificate SHA-256 {}",
+        gemini.bind, gemini.port, fingerprint
+    );
+    let capsule = Arc::new(Capsule {
+        source,
+        hostname: gemini.hostname.clone(),
+        page: Cache::default(),
+    });
+    text::accept(listener, gemini.max_connections, move |stream, peer| {
+        let acceptor = acceptor.clone();
+        let capsule = capsule.clone();
+        async move { reply(stream, peer, acceptor, &capsule).await }
+    })
+    .await
+}
+
+/// What every connection answers from
+struct Capsule {
+    source: String,
+    /// Host requests must be for
+    hostname: String,
+    /// The portfolio as gemtext, loaded again once it is stale
+    page: Cache<String>,
+}
+
+/// Create a self-signed certificate for a host name and save it
+///
+/// The key already in `key_path` is used if there is one, otherwise a new
+/// key is generated and saved there too.
+fn generate_certificate(cert_path: &Path, key_path: &Path, hostname: &str) -> Result<()> {
+    let mut params = CertificateParams::new(vec![hostname.to_string()]).context("Invalid host name")?;
+    params.distinguished_name.push(DnType::CommonName, hostname);
+    if let Some(dir) = cert_path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+    }
+    let key = if key_path.exists() {
+        let pem = fs::read_to_string(key_path).with_context(|| format!("Could not read {}", key_path.display()))?;
+        KeyPair::from_pem(&pem).with_context(|| format!("Could not parse {}", key_path.display()))?
+    } else {
+        let key = KeyPair::generate().context("Could not generate a key")?;
+        write_private(key_path, key.serialize_pem().as_bytes())
+            .with_context(|| format!("Could not write {}", key_path.display()))?;
+        key
+    };
+    let cert = params.self_signed(&key).context("Could not generate a certificate")?;
+    fs::write(cert_path, cert.pem()).with_context(|| format!("Could not write {}", cert_path.display()))?;
+    println!("Created certificate {}", cert_path.display());
+    Ok(())
+}
+
+/// Read a PEM certificate chain and private key
+fn load_certificate(cert_path: &Path, key_path: &Path) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+    let read = |path: &Path| fs::read(path).with_context(|| format!("Could not read {}", path.display()));
+    let certs = CertificateDer::pem_slice_iter(&read(cert_path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Could not parse {}", cert_path.display()))?;
+    if certs.is_empty() {
+        return Err(anyhow!("There is no certificate in {}", cert_path.display()));
+    }
+    let key = PrivateKeyDer::from_pem_slice(&read(key_path)?)
+        .with_context(|| format!("Could not parse {}", key_path.display()))?;
+    Ok((certs, key))
+}
+
+/// SHA-256 of a certificate in hex, as Gemini clients show it
+fn fingerprint(cert: &CertificateDer) -> String {
+    ring::digest::digest(&ring::digest::SHA256, cert)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Answer the request of one connection
+async fn reply(stream: TcpStream, peer: SocketAddr, acceptor: TlsAcceptor, capsule: &Capsule) -> Result<()> {
+    let mut stream = time::timeout(text::REQUEST_TIMEOUT, acceptor.accept(stream))
+        .await
+        .context("The TLS handshake took too long")?
+        .context("The TLS handshake failed")?;
+    let request = text::read_request(&mut stream).await?;
+    println!("{} asked for {:?}", peer, request);
+
+    let response = respond(capsule, &request).await;
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Response header and body for a request URL
+async fn respond(capsule: &Capsule, request: &str) -> String {
+    let Some(rest) = request.strip_prefix(SCHEME) else {
+        return "53 Only gemini:// URLs are served here\r\n".to_string();
+    };
+    let (authority, path) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+    if !host(authority).eq_ignore_ascii_case(&capsule.hostname) {
+        return format!("53 Only gemini://{}/ is served here\r\n", capsule.hostname);
+    }
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    if !matches!(path, "" | "/" | "/index.gmi") {
+        return "51 Not found\r\n".to_string();
+    }
+
+    match capsule.page.get(|| page(&capsule.source)).await {
+        Ok(page) => format!("20 text/gemini; charset=utf-8\r\n{}", page),
+        Err(err) => {
+            eprintln!("{:#}", err);
+            "40 Could not load the portfolio\r\n".to_string()
+        }
+    }
+}
+
+/// The portfolio as gemtext
+async fn page(source: &str) -> Result<String> {
+    let portfolio = data::load(source).await?;
+    let formatted = Formatter::new().format(&portfolio);
+    Ok(gemtext::render(&portfolio, &formatted))
+}
+
+/// Host of a URL authority, without the port or the trailing dot of a fully
+/// qualified name
+fn host(authority: &str) -> &str {
+    let host = match authority.strip_prefix('[') {
+        // IPv6 addresses are bracketed and contain colons themselves
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+    host.strip_suffix('.').unwrap_or(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hosts_lose_their_port() {
+        assert_eq!(host("example.com"), "example.com");
+        assert_eq!(host("example.com:1965"), "example.com");
+        assert_eq!(host("example.com.:1965"), "example.com");
+        assert_eq!(host("[::1]:1965"), "::1");
+    }
+
+    #[tokio::test]
+    async fn other_hosts_are_refused() {
+        let capsule = Capsule {
+            source: String::new(),
+            hostname: "example.com".to_string(),
+            page: Cache::default(),
+        };
+        assert!(respond(&capsule, "gemini://elsewhere.org/").await.starts_with("53 "));
+        assert!(respond(&capsule, "gemini://example.com.evil/").await.starts_with("53 "));
+        assert!(respond(&capsule, "gemini://Example.com:1965/missing").await.starts_with("51 "));
+    }
+}
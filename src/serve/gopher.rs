@@ -0,0 +1,265 @@
+//! Gopher server
+//!
+//! Serves the portfolio as Gopher menus and text documents (RFC 1436). The
+//! root menu links the home page and a menu for every content section, and
+//! each section menu links a text document per item. Selectors are `home`,
+//! `all`, a section's navigation index like `2`, and an item within it like
+//! `2/3`.
+
+use anyhow::Result;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use super::text::{self, Printer};
+use crate::config::args::GopherArgs;
+use crate::processor::formatter::FormattedPortfolio;
+use crate::ui::print::{self, Page};
+
+/// Item type of text documents
+const TEXT: char = '0';
+
+/// Item type of menus
+const MENU: char = '1';
+
+/// Item type of lines that only show text, a widely supported extension
+const INFO: char = 'i';
+
+/// Item type of errors
+const ERROR: char = '3';
+
+/// State shared by all connections
+struct Shared {
+    printer: Printer,
+    host: Host,
+}
+
+/// Where clients reach the server, for links in menus
+struct Host {
+    name: String,
+    port: u16,
+}
+
+/// Documents and menus a selector can ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resource {
+    /// Menu of the home page and sections
+    Root,
+    /// Name, contacts and about text
+    Home,
+    /// The whole portfolio as one document
+    All,
+    /// Menu of a section's items, by index
+    Section(usize),
+    /// An item by section and item index
+    Item(usize, usize),
+}
+
+/// Answer Gopher requests until the server fails
+pub async fn serve(printer: Printer, gopher: &GopherArgs) -> Result<()> {
+    let listener = text::listen(&gopher.bind, gopher.port).await?;
+    println!("Serving Gopher on {}:{}", gopher.bind, gopher.port);
+    let host = Host {
+        name: gopher.hostname.clone(),
+        port: gopher.public_port.unwrap_or(gopher.port),
+    };
+    run(listener, printer, host, gopher.max_connections).await
+}
+
+/// Answer Gopher requests on a listener, with menus linking `host`
+async fn run(listener: TcpListener, printer: Printer, host: Host, max_connections: usize) -> Result<()> {
+    let shared = Arc::new(Shared { printer, host });
+    text::answer(listener, max_connections, move |selector| {
+        let shared = shared.clone();
+        async move { respond(&shared.printer, &shared.host, &selector).await }
+    })
+    .await
+}
+
+/// Response to a selector
+async fn respond(printer: &Printer, host: &Host, selector: &str) -> String {
+    // Gopher+ clients send more fields after a tab
+    let selector = selector.split('\t').next().unwrap_or_default().trim_matches('/');
+    let portfolio = match printer.load().await {
+        Ok(portfolio) => portfolio,
+        Err(err) => {
+            // Details can name local paths, so they only go to the log
+            eprintln!("{:#}", err);
+            return error("Portfolio unavailable");
+        }
+    };
+    let Some(resource) = resolve(&portfolio, selector) else {
+        return error(&format!("Nothing here for '{}'", selector));
+    };
+
+    match resource {
+        Resource::Root => root_menu(&portfolio, host),
+        Resource::Home => document(&printer.pages(&portfolio, &[Page::Home])),
+        Resource::All => document(&printer.pages(&portfolio, &print::all_pages(&portfolio))),
+        Resource::Section(index) => section_menu(&portfolio, index, host),
+        Resource::Item(section, item) => document(&printer.item(&portfolio.content_sections[section].items[item])),
+    }
+}
+
+/// Resource a selector asks for, if it exists
+fn resolve(portfolio: &FormattedPortfolio, selector: &str) -> Option<Resource> {
+    match selector {
+        "" => return Some(Resource::Root),
+        "home" => return Some(Resource::Home),
+        "all" => return Some(Resource::All),
+        _ => {}
+    }
+    // Sections and items are numbered from 1, like the navigation keys
+    let index = |number: &str, count: usize| {
+        number
+            .parse::<usize>()
+            .ok()
+            .filter(|&number| (1..=count).contains(&number))
+            .map(|number| number - 1)
+    };
+    let sections = &portfolio.content_sections;
+    match selector.split_once('/') {
+        None => index(selector, sections.len()).map(Resource::Section),
+        Some((section, item)) => {
+            let section = index(section, sections.len())?;
+            let item = index(item, sections[section].items.len())?;
+            Some(Resource::Item(section, item))
+        }
+    }
+}
+
+/// Menu linking the home page and every section
+fn root_menu(portfolio: &FormattedPortfolio, host: &Host) -> String {
+    let mut menu = Menu::new(host);
+    menu.entry(INFO, &format!("{} - {}", portfolio.name, portfolio.title), "");
+    menu.entry(INFO, "", "");
+    menu.entry(TEXT, "Home", "home");
+    for (i, section) in portfolio.content_sections.iter().enumerate() {
+        menu.entry(MENU, &section.title, &(i + 1).to_string());
+    }
+    menu.entry(INFO, "", "");
+    menu.entry(TEXT, "Everything as one document", "all");
+    menu.finish()
+}
+
+/// Menu linking every item of a section
+fn section_menu(portfolio: &FormattedPortfolio, index: usize, host: &Host) -> String {
+    let section = &portfolio.content_sections[index];
+    let mut menu = Menu::new(host);
+    menu.entry(INFO, &section.title, "");
+    menu.entry(INFO, "", "");
+    for (i, item) in section.items.iter().enumerate() {
+        let title = [&item.title, &item.sub_title]
+            .into_iter()
+            .find(|title| !title.is_empty())
+            .cloned()
+            .unwrap_or_else(|| format!("Item {}", i + 1));
+        menu.entry(TEXT, &title, &format!("{}/{}", index + 1, i + 1));
+    }
+    menu.finish()
+}
+
+/// Gopher menu being written
+struct Menu<'a> {
+    host: &'a Host,
+    text: String,
+}
+
+impl<'a> Menu<'a> {
+    fn new(host: &'a Host) -> Self {
+        Self { host, text: String::new() }
+    }
+
+    /// Add an entry linking a selector on this server
+    fn entry(&mut self, kind: char, display: &str, selector: &str) {
+        self.text.push_str(&format!(
+            "{}{}\t{}\t{}\t{}\r\n",
+            kind,
+            field(display),
+            field(selector),
+            self.host.name,
+            self.host.port
+        ));
+    }
+
+    /// Menu text with the closing line
+    fn finish(mut self) -> String {
+        self.text.push_str(".\r\n");
+        self.text
+    }
+}
+
+/// Text document, with lines starting with a dot escaped
+fn document(lines: &[String]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        if line.starts_with('.') {
+            text.push('.');
+        }
+        text.push_str(line);
+        text.push_str("\r\n");
+    }
+    text.push_str(".\r\n");
+    text
+}
+
+/// Menu with a single error entry
+fn error(message: &str) -> String {
+    format!("{}{}\t\terror.host\t1\r\n.\r\n", ERROR, field(message))
+}
+
+/// Menu field without the tabs and line breaks that separate fields
+fn field(text: &str) -> String {
+    text.replace(['\t', '\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serve::testing::{ask, local_listener, Config};
+
+    const YAML: &str = "name: Test Person\ntitle: Tester\ncontent:\n  - title: Projects\n    layout: list\n    content:\n      - title: Thing\n        description: Made a thing\n      - title: Other\n        description: Another one\n  - title: Talks\n    content: Gave a talk\n";
+
+    /// Serve the test portfolio on a local port
+    async fn server(config: &Config) -> std::net::SocketAddr {
+        let (listener, addr) = local_listener().await;
+        let host = Host { name: "gopher.test".to_string(), port: 70 };
+        tokio::spawn(run(listener, config.printer(), host, 4));
+        addr
+    }
+
+    #[tokio::test]
+    async fn the_root_menu_links_every_section() {
+        let config = Config::new("gopher-root", YAML);
+        let addr = server(&config).await;
+
+        let menu = ask(addr, b"\r\n").await;
+        let entries: Vec<Vec<&str>> = menu
+            .lines()
+            .filter(|line| line.starts_with([TEXT, MENU]))
+            .map(|line| line.trim_end_matches('\r').split('\t').collect())
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ["0Home", "home", "gopher.test", "70"],
+                ["1Projects", "1", "gopher.test", "70"],
+                ["1Talks", "2", "gopher.test", "70"],
+                ["0Everything as one document", "all", "gopher.test", "70"],
+            ]
+        );
+        assert!(menu.ends_with("\r\n.\r\n"));
+    }
+
+    #[tokio::test]
+    async fn items_are_text_documents() {
+        let config = Config::new("gopher-item", YAML);
+        let addr = server(&config).await;
+
+        let menu = ask(addr, b"1\r\n").await;
+        assert!(menu.contains("0Thing\t1/1\t") && menu.contains("0Other\t1/2\t"));
+        let document = ask(addr, b"/1/1\r\n").await;
+        assert!(document.contains("Thing") && document.contains("Made a thing") && !document.contains("Another one"));
+        assert!(document.ends_with("\r\n.\r\n"));
+        assert!(ask(addr, b"1/9\r\n").await.starts_with(ERROR));
+    }
+}
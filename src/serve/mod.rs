@@ -0,0 +1,199 @@
+//! Serving the portfolio
+//!
+//! Long-running servers that show the portfolio to other machines. Each
+//! protocol lives in its own module; the interactive ones start a
+//! [`Viewer`] per visitor and the plain-text ones answer with a
+//! [`text::Printer`].
+
+pub mod finger;
+pub mod gemini;
+pub mod gopher;
+pub mod ssh;
+pub mod text;
+pub mod web;
+
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use self::text::{Cache, Printer};
+use crate::config::args::{ServeArgs, ServeProtocol};
+use crate::config::Args;
+use crate::ui::app::App;
+use crate::ui::color::ColorDepth;
+use crate::ui::events::{Event, EventHandler};
+use crate::ui::keymap::Keymap;
+use crate::ui::terminal::{RemoteFrames, RemoteOutput, TerminalSession};
+use crate::ui::theme::ThemeSet;
+
+/// Run the server for the requested protocol until it fails or is stopped
+pub async fn serve(args: &Args, serve: &ServeArgs, keymap: Keymap, themes: ThemeSet) -> Result<()> {
+    let viewer = Viewer {
+        args: args.clone(),
+        keymap,
+        themes,
+        source: Arc::default(),
+    };
+    match serve.protocol {
+        ServeProtocol::Ssh(ref ssh) => ssh::serve(viewer, ssh).await,
+        ServeProtocol::Web(ref web) => web::serve(viewer, web).await,
+        ServeProtocol::Finger(ref finger) => finger::serve(Printer::new(args), finger).await,
+        ServeProtocol::Gopher(ref gopher) => gopher::serve(Printer::new(args), gopher).await,
+        ServeProtocol::Gemini(ref gemini) => gemini::serve(args.config_path.clone(), gemini).await,
+    }
+}
+
+/// Settings every remote viewer starts from
+pub struct Viewer {
+    args: Args,
+    keymap: Keymap,
+    /// Themes in full color, reduced for each visitor's terminal
+    themes: ThemeSet,
+    /// Source loaded once for all visitors and again once it is stale
+    source: Arc<Cache<String>>,
+}
+
+/// A viewer running for one visitor
+pub struct Session {
+    /// Input and resize events for the viewer
+    pub input: mpsc::UnboundedSender<Event>,
+    /// Frames to send to the visitor's terminal; ends when the viewer quits
+    pub output: RemoteFrames,
+}
+
+impl Viewer {
+    /// Start a viewer on a remote terminal of the given size
+    ///
+    /// `name` identifies the visitor in the log. The viewer runs on its own
+    /// task until it quits or receives [`Event::Terminate`].
+    pub fn start(&self, name: String, size: (u16, u16), depth: ColorDepth) -> Result<Session> {
+        let (output, frames) = RemoteOutput::new();
+        let terminal = TerminalSession::remote(output, size, !self.args.no_mouse)?;
+        let themes = self.themes.for_depth(depth);
+        let mut app = App::remote(self.args.clone(), self.keymap.clone(), themes, terminal, self.source.clone())?;
+        let (events, input) = EventHandler::remote();
+
+        println!("{} connected", name);
+        tokio::spawn(async move {
+            if let Err(err) = app.run_with(events).await {
+                eprintln!("{}: {:#}", name, err);
+            }
+            println!("{} disconnected", name);
+        });
+        Ok(Session { input, output: frames })
+    }
+}
+
+/// Write a file only the owner can read
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    fs::OpenOptions::new().write(true).create_new(true).mode(0o600).open(path)?.write_all(contents)
+}
+
+/// Write a file only the owner can read
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    fs::write(path, contents)
+}
+
+/// Helpers for testing the servers with a local client
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::path::PathBuf;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Portfolio config file, removed when dropped
+    pub struct Config {
+        path: PathBuf,
+    }
+
+    impl Config {
+        /// Write `yaml` to a temporary file named after `name`
+        pub fn new(name: &str, yaml: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("portfolio-tui-{}-{}.yml", name, std::process::id()));
+            fs::write(&path, yaml).unwrap();
+            Self { path }
+        }
+
+        /// Arguments loading the config
+        pub fn args(&self) -> Args {
+            <Args as clap::Parser>::parse_from(["portfolio-tui", "--config-path", self.path.to_str().unwrap()])
+        }
+
+        /// Printer loading the config
+        pub fn printer(&self) -> Printer {
+            Printer::new(&self.args())
+        }
+
+        /// Viewer loading the config with the default keys and themes
+        pub fn viewer(&self) -> Viewer {
+            let themes = ThemeSet::load(None, ColorDepth::TrueColor).unwrap();
+            Viewer { args: self.args(), keymap: Keymap::default(), themes, source: Arc::default() }
+        }
+    }
+
+    impl Drop for Config {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    /// Listener on a free local port, with its address
+    pub async fn local_listener() -> (TcpListener, SocketAddr) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        (listener, addr)
+    }
+
+    /// Send `request` and read the response until the server closes
+    pub async fn ask(addr: SocketAddr, request: &[u8]) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request).await.unwrap();
+        let mut response = Vec::new();
+        // A rejected request may end in a reset instead of a clean close
+        let _ = stream.read_to_end(&mut response).await;
+        String::from_utf8(response).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::Config;
+    use super::*;
+    use std::time::Duration;
+    use tokio::time;
+
+    /// Read frames until one contains `text`
+    async fn wait_for(session: &mut Session, text: &str) {
+        let mut drawn = Vec::new();
+        while !String::from_utf8_lossy(&drawn).contains(text) {
+            let frame = time::timeout(Duration::from_secs(5), session.output.recv()).await.unwrap().unwrap();
+            drawn.extend(frame);
+        }
+    }
+
+    #[tokio::test]
+    async fn viewers_share_the_loaded_source() {
+        let config = Config::new("shared", "name: Test\ndescription: About me\n");
+        let viewer = config.viewer();
+        let mut first = viewer.start("first".to_string(), (80, 24), ColorDepth::TrueColor).unwrap();
+        wait_for(&mut first, "About Me").await;
+
+        // The second viewer does not read the source again
+        drop(config);
+        let mut second = viewer.start("second".to_string(), (80, 24), ColorDepth::TrueColor).unwrap();
+        wait_for(&mut second, "About Me").await;
+
+        for session in [first, second] {
+            session.input.send(Event::Terminate).unwrap();
+        }
+    }
+}
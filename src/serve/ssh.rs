@@ -0,0 +1,336 @@
+//! SSH server
+//!
+//! Runs the viewer for everyone who connects with `ssh`. Each session gets
+//! its own [`App`] drawing on the SSH channel, sized by the client's pty and
+//! resized when its window changes. Sessions authenticate with the "none"
+//! method, so no password or key is needed; `--allow-users` limits the user
+//! names that are accepted.
+
+use anyhow::{Context, Result};
+use russh::keys::ssh_key::LineEnding;
+use russh::keys::{Algorithm, HashAlg, PrivateKey, PublicKey};
+use russh::server::{Auth, Config, Handler, Msg, Server, Session};
+use russh::{Channel, ChannelId, ChannelOpenFailure, Pty};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore};
+
+use super::{write_private, Viewer};
+use crate::config::args::SshArgs;
+use crate::config::Settings;
+use crate::ui::color::ColorDepth;
+use crate::ui::events::Event;
+use crate::ui::input::Decoder;
+
+/// File name of the generated host key in the config directory
+const HOST_KEY_FILE: &str = "ssh_host_ed25519_key";
+
+/// Connections without any traffic for this long are closed
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Terminal size used when the client does not request a pty
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Listen for SSH connections until the server fails
+pub async fn serve(viewer: Viewer, ssh: &SshArgs) -> Result<()> {
+    let key_path = match ssh.host_key {
+        Some(ref path) => PathBuf::from(path),
+        None => Settings::config_dir()
+            .context("Could not find the config directory for the host key, pass --host-key")?
+            .join(HOST_KEY_FILE),
+    };
+    let key = host_key(&key_path)?;
+    let fingerprint = key.public_key().fingerprint(HashAlg::Sha256);
+
+    let config = Config {
+        inactivity_timeout: Some(IDLE_TIMEOUT),
+        auth_rejection_time: Duration::from_secs(1),
+        auth_rejection_time_initial: Some(Duration::ZERO),
+        keys: vec![key],
+        nodelay: true,
+        ..Default::default()
+    };
+    let mut server = PortfolioServer {
+        shared: Arc::new(Shared {
+            viewer,
+            allow_users: ssh.allow_users.clone(),
+            slots: Arc::new(Semaphore::new(ssh.max_connections)),
+        }),
+    };
+
+    println!("Serving over SSH on {}:{}, host key {}", ssh.bind, ssh.port, fingerprint);
+    server
+        .run_on_address(Arc::new(config), (ssh.bind.as_str(), ssh.port))
+        .await
+        .with_context(|| format!("Could not serve on {}:{}", ssh.bind, ssh.port))
+}
+
+/// Load the host key, generating and saving an Ed25519 key if there is none
+fn host_key(path: &Path) -> Result<PrivateKey> {
+    if path.exists() {
+        return russh::keys::load_secret_key(path, None)
+            .with_context(|| format!("Could not read host key {}", path.display()));
+    }
+
+    let key = PrivateKey::random(&mut rand::rng(), Algorithm::Ed25519).context("Could not generate a host key")?;
+    let pem = key.to_openssh(LineEnding::LF).context("Could not encode the host key")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+    }
+    write_private(path, pem.as_bytes()).with_context(|| format!("Could not write host key {}", path.display()))?;
+    println!("Created host key {}", path.display());
+    Ok(key)
+}
+
+/// State shared by all connections
+struct Shared {
+    viewer: Viewer,
+    /// Accepted user names, anyone when empty
+    allow_users: Vec<String>,
+    /// One permit per viewer that may be running
+    slots: Arc<Semaphore>,
+}
+
+/// Server creating a handler per connection
+struct PortfolioServer {
+    shared: Arc<Shared>,
+}
+
+impl Server for PortfolioServer {
+    type Handler = Connection;
+
+    fn new_client(&mut self, peer: Option<SocketAddr>) -> Connection {
+        Connection {
+            shared: self.shared.clone(),
+            peer: peer.map_or_else(|| "unknown".to_string(), |peer| peer.to_string()),
+            user: String::new(),
+            term: String::new(),
+            size: DEFAULT_SIZE,
+            channel: None,
+            input: None,
+            decoder: Decoder::default(),
+        }
+    }
+}
+
+/// State of one SSH connection
+struct Connection {
+    shared: Arc<Shared>,
+    /// Client address, for the log
+    peer: String,
+    /// Authenticated user name
+    user: String,
+    /// Terminal type from the pty request
+    term: String,
+    /// Terminal width and height
+    size: (u16, u16),
+    /// The session channel, once the client opened one
+    channel: Option<ChannelId>,
+    /// Events for the running session, once the shell started
+    input: Option<mpsc::UnboundedSender<Event>>,
+    /// Turns the client's key presses into events
+    decoder: Decoder,
+}
+
+impl Connection {
+    /// Accept users on the allow list, or anyone without one
+    fn authenticate(&mut self, user: &str) -> Auth {
+        let allowed = self.shared.allow_users.is_empty() || self.shared.allow_users.iter().any(|name| name == user);
+        if !allowed {
+            return Auth::reject();
+        }
+        self.user = user.to_string();
+        Auth::Accept
+    }
+
+    /// Start a viewer on the channel
+    ///
+    /// Frames are forwarded to the client as they are drawn, and the channel
+    /// is closed when the viewer quits. The viewer holds `permit` until then.
+    fn start(&mut self, channel: ChannelId, session: &Session, permit: OwnedSemaphorePermit) -> Result<()> {
+        let name = format!("{}@{}", self.user, self.peer);
+        let depth = ColorDepth::from_term(&self.term);
+        let mut running = self.shared.viewer.start(name, self.size, depth)?;
+        self.input = Some(running.input);
+
+        let handle = session.handle();
+        tokio::spawn(async move {
+            while let Some(frame) = running.output.recv().await {
+                if handle.data(channel, frame).await.is_err() {
+                    return;
+                }
+            }
+            let _ = handle.exit_status_request(channel, 0).await;
+            let _ = handle.eof(channel).await;
+            let _ = handle.close(channel).await;
+            drop(permit);
+        });
+        Ok(())
+    }
+
+    /// Pass an event to the running session
+    fn send(&self, event: Event) {
+        if let Some(ref input) = self.input {
+            let _ = input.send(event);
+        }
+    }
+}
+
+impl Handler for Connection {
+    type Error = anyhow::Error;
+
+    async fn auth_none(&mut self, user: &str) -> Result<Auth> {
+        Ok(self.authenticate(user))
+    }
+
+    async fn auth_publickey(&mut self, user: &str, _key: &PublicKey) -> Result<Auth> {
+        Ok(self.authenticate(user))
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        channel: Channel<Msg>,
+        reply: russh::server::ChannelOpenHandle,
+        _session: &mut Session,
+    ) -> Result<()> {
+        // One viewer per connection
+        if self.channel.is_some() {
+            reply.reject(ChannelOpenFailure::AdministrativelyProhibited).await;
+            return Ok(());
+        }
+        self.channel = Some(channel.id());
+        reply.accept().await;
+        Ok(())
+    }
+
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        term: &str,
+        width: u32,
+        height: u32,
+        _pixel_width: u32,
+        _pixel_height: u32,
+        _modes: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> Result<()> {
+        self.term = term.to_string();
+        self.size = (clamp(width), clamp(height));
+        session.channel_success(channel)?;
+        Ok(())
+    }
+
+    async fn shell_request(&mut self, channel: ChannelId, session: &mut Session) -> Result<()> {
+        let Ok(permit) = self.shared.slots.clone().try_acquire_owned() else {
+            session.channel_success(channel)?;
+            session.data(channel, &b"Too many visitors, try again later\r\n"[..])?;
+            session.exit_status_request(channel, 1)?;
+            session.eof(channel)?;
+            session.close(channel)?;
+            return Ok(());
+        };
+        match self.start(channel, session, permit) {
+            Ok(()) => session.channel_success(channel)?,
+            Err(err) => {
+                eprintln!("{}@{}: {:#}", self.user, self.peer, err);
+                session.channel_failure(channel)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn exec_request(&mut self, channel: ChannelId, _command: &[u8], session: &mut Session) -> Result<()> {
+        // Only the interactive viewer is offered
+        session.channel_failure(channel)?;
+        Ok(())
+    }
+
+    async fn data(&mut self, _channel: ChannelId, data: &[u8], _session: &mut Session) -> Result<()> {
+        for event in self.decoder.decode(data) {
+            self.send(event);
+        }
+        Ok(())
+    }
+
+    async fn window_change_request(
+        &mut self,
+        _channel: ChannelId,
+        width: u32,
+        height: u32,
+        _pixel_width: u32,
+        _pixel_height: u32,
+        _session: &mut Session,
+    ) -> Result<()> {
+        self.send(Event::Resize(clamp(width), clamp(height)));
+        Ok(())
+    }
+
+    async fn channel_eof(&mut self, _channel: ChannelId, _session: &mut Session) -> Result<()> {
+        self.send(Event::Terminate);
+        Ok(())
+    }
+
+    async fn channel_close(&mut self, _channel: ChannelId, _session: &mut Session) -> Result<()> {
+        self.send(Event::Terminate);
+        Ok(())
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // Stop the viewer when the client goes away without closing the channel
+        self.send(Event::Terminate);
+    }
+}
+
+/// Terminal dimension from the client, kept within what can be drawn
+fn clamp(cells: u32) -> u16 {
+    cells.clamp(1, u16::MAX as u32) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serve::testing::Config as PortfolioConfig;
+    use russh::client;
+    use russh::keys::PublicKeyOrCertificate;
+
+    /// Client accepting any host key
+    struct Client;
+
+    impl client::Handler for Client {
+        type Error = russh::Error;
+
+        async fn check_server_key(&mut self, _key: &PublicKeyOrCertificate) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn a_second_session_channel_is_rejected() {
+        let portfolio = PortfolioConfig::new("ssh", "name: Test\n");
+        let key = PrivateKey::random(&mut rand::rng(), Algorithm::Ed25519).unwrap();
+        let config = Config { keys: vec![key], ..Default::default() };
+        let mut server = PortfolioServer {
+            shared: Arc::new(Shared {
+                viewer: portfolio.viewer(),
+                allow_users: Vec::new(),
+                slots: Arc::new(Semaphore::new(4)),
+            }),
+        };
+        let (client_stream, server_stream) = tokio::io::duplex(1 << 16);
+        let connection = server.new_client(None);
+        tokio::spawn(async move { russh::server::run_stream(Arc::new(config), server_stream, connection).await?.await });
+
+        let mut client = client::connect_stream(Arc::new(client::Config::default()), client_stream, Client).await.unwrap();
+        assert!(client.authenticate_none("visitor").await.unwrap().success());
+        let _first = client.channel_open_session().await.unwrap();
+        match client.channel_open_session().await {
+            Err(russh::Error::ChannelOpenFailure(ChannelOpenFailure::AdministrativelyProhibited)) => {}
+            other => panic!("expected the channel to be rejected, got {:?}", other.map(|channel| channel.id())),
+        }
+    }
+}
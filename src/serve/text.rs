@@ -0,0 +1,307 @@
+//! Plain-text servers
+//!
+//! Finger and Gopher answer one request line per connection with text laid
+//! out like the `print` command. The portfolio is loaded again once it is a
+//! few seconds old, so edits to the source show up without a restart.
+
+use anyhow::{anyhow, bail, Context, Result};
+use ratatui::text::Line;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time;
+
+use crate::config::Args;
+use crate::data;
+use crate::processor::formatter::{FormattedContentItem, FormattedPortfolio};
+use crate::processor::Formatter;
+use crate::ui::icons::{IconMode, Icons};
+use crate::ui::print::{self, Page};
+use crate::ui::theme::Theme;
+
+/// Column text is wrapped to, narrow enough for classic clients
+pub const WIDTH: usize = 70;
+
+/// How long a client may take to send its request
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest request line accepted, in bytes
+const MAX_REQUEST: u64 = 1024;
+
+/// How long a loaded portfolio is reused before the source is read again
+const CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// How long a load may take before it is given up
+const LOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Value that is loaded again once it is older than [`CACHE_TTL`]
+///
+/// One request at a time loads it. Meanwhile, and when that load fails or
+/// takes longer than [`LOAD_TIMEOUT`], requests get the stale value if there
+/// is one. Failed loads are not kept.
+pub struct Cache<T> {
+    value: std::sync::Mutex<Option<(Instant, Arc<T>)>>,
+    /// Held by the request loading a new value
+    loading: Mutex<()>,
+}
+
+impl<T> Cache<T> {
+    /// The cached value, or a new one from `load` if there is none or it is stale
+    pub async fn get<F, R>(&self, load: F) -> Result<Arc<T>>
+    where
+        F: FnOnce() -> R,
+        R: Future<Output = Result<T>>,
+    {
+        let stale = match self.current() {
+            Some((loaded, value)) if loaded.elapsed() < CACHE_TTL => return Ok(value),
+            cached => cached.map(|(_, value)| value),
+        };
+        let _loading = match self.loading.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => match stale {
+                Some(stale) => return Ok(stale),
+                // Wait for the first load instead of starting another
+                None => {
+                    let _loaded = self.loading.lock().await;
+                    return self
+                        .current()
+                        .map(|(_, value)| value)
+                        .ok_or_else(|| anyhow!("The portfolio could not be loaded"));
+                }
+            },
+        };
+
+        let error = match time::timeout(LOAD_TIMEOUT, load()).await {
+            Ok(Ok(fresh)) => {
+                let fresh = Arc::new(fresh);
+                *self.value.lock().unwrap() = Some((Instant::now(), fresh.clone()));
+                return Ok(fresh);
+            }
+            Ok(Err(err)) => err,
+            Err(_) => anyhow!("Loading took longer than {} seconds", LOAD_TIMEOUT.as_secs()),
+        };
+        match stale {
+            Some(stale) => {
+                eprintln!("Serving the previous copy: {:#}", error);
+                Ok(stale)
+            }
+            None => Err(error),
+        }
+    }
+
+    /// The value with the time it was loaded, if there is one
+    fn current(&self) -> Option<(Instant, Arc<T>)> {
+        let value = self.value.lock().unwrap();
+        value.as_ref().map(|(loaded, value)| (*loaded, value.clone()))
+    }
+}
+
+impl<T> Default for Cache<T> {
+    fn default() -> Self {
+        Self {
+            value: std::sync::Mutex::new(None),
+            loading: Mutex::new(()),
+        }
+    }
+}
+
+/// Loads the portfolio and lays it out as lines of text
+pub struct Printer {
+    source: String,
+    icons: Icons,
+    /// Styles are dropped, so any theme lays out the same
+    theme: Theme,
+    portfolio: Cache<FormattedPortfolio>,
+}
+
+impl Printer {
+    /// Printer for the source and icon flags on the command line
+    pub fn new(args: &Args) -> Self {
+        Self {
+            source: args.config_path.clone(),
+            icons: Icons::new(IconMode::from_flags(args.ascii, args.nerd_fonts)),
+            theme: Theme::dark(),
+            portfolio: Cache::default(),
+        }
+    }
+
+    /// The formatted portfolio, loaded again once it is stale
+    pub async fn load(&self) -> Result<Arc<FormattedPortfolio>> {
+        self.portfolio
+            .get(|| async {
+                let portfolio = data::load(&self.source).await?;
+                Ok(Formatter::with_max_width(WIDTH).format(&portfolio))
+            })
+            .await
+    }
+
+    /// Text of the given pages, separated by blank lines
+    pub fn pages(&self, portfolio: &FormattedPortfolio, pages: &[Page]) -> Vec<String> {
+        plain(print::pages_lines(portfolio, pages, &self.icons, WIDTH, &self.theme))
+    }
+
+    /// Text of a single item
+    pub fn item(&self, item: &FormattedContentItem) -> Vec<String> {
+        plain(print::item_lines(item, &self.icons, WIDTH, &self.theme))
+    }
+}
+
+/// Lines without their styles
+fn plain(lines: Vec<Line>) -> Vec<String> {
+    lines.iter().map(print::plain).collect()
+}
+
+/// Listen on an address and port
+pub async fn listen(bind: &str, port: u16) -> Result<TcpListener> {
+    TcpListener::bind((bind, port))
+        .await
+        .with_context(|| format!("Could not listen on {}:{}", bind, port))
+}
+
+/// Answer the request line of every connection with `respond`
+///
+/// Each connection is closed after the response.
+pub async fn answer<F, R>(listener: TcpListener, max_connections: usize, respond: F) -> Result<()>
+where
+    F: Fn(String) -> R + Send + Sync + 'static,
+    R: Future<Output = String> + Send,
+{
+    let respond = Arc::new(respond);
+    accept(listener, max_connections, move |stream, peer| {
+        let respond = respond.clone();
+        async move { reply(stream, peer, &*respond).await }
+    })
+    .await
+}
+
+/// Handle every connection on its own task until accepting fails
+///
+/// At most `max_connections` are handled at once; connections beyond that
+/// are closed right away.
+pub async fn accept<F, R>(listener: TcpListener, max_connections: usize, handle: F) -> Result<()>
+where
+    F: Fn(TcpStream, SocketAddr) -> R,
+    R: Future<Output = Result<()>> + Send + 'static,
+{
+    let slots = Arc::new(Semaphore::new(max_connections));
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            // The client gave up before the connection was accepted
+            Err(err) if err.kind() == std::io::ErrorKind::ConnectionAborted => continue,
+            Err(err) => return Err(err).context("Could not accept a connection"),
+        };
+        let Ok(permit) = slots.clone().try_acquire_owned() else {
+            continue;
+        };
+        let connection = handle(stream, peer);
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                eprintln!("{}: {:#}", peer, err);
+            }
+            drop(permit);
+        });
+    }
+}
+
+/// Read the request of one connection and send the response
+async fn reply<F, R>(mut stream: TcpStream, peer: SocketAddr, respond: &F) -> Result<()>
+where
+    F: Fn(String) -> R,
+    R: Future<Output = String>,
+{
+    let request = read_request(&mut stream).await?;
+    println!("{} asked for {:?}", peer, request);
+    let response = respond(request).await;
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read a request line, without its line ending
+pub async fn read_request<S: AsyncRead + Unpin>(stream: &mut S) -> Result<String> {
+    let mut line = Vec::new();
+    let mut reader = BufReader::new(stream.take(MAX_REQUEST));
+    time::timeout(REQUEST_TIMEOUT, reader.read_until(b'\n', &mut line))
+        .await
+        .context("The request took too long")?
+        .context("Could not read the request")?;
+    if !line.ends_with(b"\n") {
+        bail!("The request line was cut off");
+    }
+    let line = String::from_utf8(line).context("The request is not valid UTF-8")?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serve::testing::{ask, local_listener};
+    use super::*;
+    use std::future;
+
+    /// Cache holding a value loaded longer than [`CACHE_TTL`] ago
+    fn stale_cache(value: u32) -> Arc<Cache<u32>> {
+        let cache = Cache::default();
+        let loaded = Instant::now().checked_sub(CACHE_TTL).unwrap();
+        *cache.value.lock().unwrap() = Some((loaded, Arc::new(value)));
+        Arc::new(cache)
+    }
+
+    #[tokio::test]
+    async fn a_fresh_value_is_reused() {
+        let cache = Cache::default();
+        let first = cache.get(|| async { Ok(1) }).await.unwrap();
+        let second = cache.get(|| async { Ok(2) }).await.unwrap();
+        assert_eq!((*first, *second), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn failed_loads_are_not_kept() {
+        let cache = Cache::default();
+        assert!(cache.get(|| async { Err(anyhow!("unreadable")) }).await.is_err());
+        assert_eq!(*cache.get(|| async { Ok(2) }).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn a_stale_value_is_served_while_a_load_hangs() {
+        let cache = stale_cache(1);
+        let hanging = tokio::spawn({
+            let cache = cache.clone();
+            async move { cache.get(future::pending).await }
+        });
+        tokio::task::yield_now().await;
+        assert_eq!(*cache.get(|| async { Ok(2) }).await.unwrap(), 1);
+        hanging.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_load_that_takes_too_long_is_given_up() {
+        let cache = stale_cache(1);
+        assert_eq!(*cache.get(future::pending).await.unwrap(), 1);
+
+        let cache = Cache::<u32>::default();
+        assert!(cache.get(future::pending).await.is_err());
+        assert_eq!(*cache.get(|| async { Ok(2) }).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn a_failed_load_serves_the_stale_value() {
+        let cache = stale_cache(1);
+        assert_eq!(*cache.get(|| async { Err(anyhow!("unreadable")) }).await.unwrap(), 1);
+        assert_eq!(*cache.get(|| async { Ok(2) }).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn overlong_requests_are_rejected() {
+        let (listener, addr) = local_listener().await;
+        tokio::spawn(answer(listener, 4, |request| async move { format!("got {}\r\n", request) }));
+
+        assert_eq!(ask(addr, b"short\r\n").await, "got short\r\n");
+        let long = format!("{}\r\n", "x".repeat(MAX_REQUEST as usize * 2));
+        assert_eq!(ask(addr, long.as_bytes()).await, "");
+    }
+}
@@ -0,0 +1,258 @@
+//! Web terminal server
+//!
+//! Serves a page with a small terminal emulator at `/` and runs a viewer for
+//! each WebSocket connection to `/ws`. Frames go to the page as binary
+//! messages and typed keys and mouse reports come back as binary messages,
+//! in the same escape sequences a terminal uses. The page reports its size
+//! with text messages like `{"type":"resize","cols":80,"rows":24}`.
+
+use anyhow::{Context, Result};
+use axum::extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
+use tokio::time::{self, Instant};
+
+use super::Viewer;
+use crate::config::args::WebArgs;
+use crate::ui::color::ColorDepth;
+use crate::ui::events::Event;
+use crate::ui::input::Decoder;
+
+/// Page with the terminal emulator
+const PAGE: &str = include_str!("terminal.html");
+
+/// Terminal size used when the page does not report one in time
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// How long to wait for the page to report its size
+const SIZE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Close code for sessions ended by the server, from RFC 6455
+const CLOSE_NORMAL: u16 = 1000;
+
+/// Close code for connections closed for being idle
+const CLOSE_AWAY: u16 = 1001;
+
+/// State shared by all connections
+struct Shared {
+    viewer: Viewer,
+    /// One permit per connection that may be open
+    slots: Arc<Semaphore>,
+    /// Time without input before a connection is closed
+    idle_timeout: Duration,
+}
+
+/// Messages the page sends as text
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ClientMessage {
+    /// The terminal has a new size
+    Resize { cols: u16, rows: u16 },
+}
+
+/// Serve the page and WebSocket until the server fails
+pub async fn serve(viewer: Viewer, web: &WebArgs) -> Result<()> {
+    let listener = TcpListener::bind((web.bind.as_str(), web.port))
+        .await
+        .with_context(|| format!("Could not listen on {}:{}", web.bind, web.port))?;
+    println!("Serving the web terminal on http://{}:{}", web.bind, web.port);
+    run(listener, viewer, web.max_connections, Duration::from_secs(web.idle_timeout)).await
+}
+
+/// Serve the page and WebSocket on a listener
+async fn run(listener: TcpListener, viewer: Viewer, max_connections: usize, idle_timeout: Duration) -> Result<()> {
+    let shared = Arc::new(Shared {
+        viewer,
+        slots: Arc::new(Semaphore::new(max_connections)),
+        idle_timeout,
+    });
+    let router = Router::new()
+        .route("/", get(page))
+        .route("/ws", get(connect))
+        .with_state(shared);
+
+    axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .context("The web server stopped")
+}
+
+/// The terminal page
+async fn page() -> Html<&'static str> {
+    Html(PAGE)
+}
+
+/// Upgrade to a WebSocket if there is room for another visitor
+async fn connect(
+    State(shared): State<Arc<Shared>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let Ok(permit) = shared.slots.clone().try_acquire_owned() else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Too many visitors, try again later").into_response();
+    };
+    upgrade.on_upgrade(move |socket| async move {
+        if let Err(err) = session(socket, &shared, peer).await {
+            eprintln!("{}: {:#}", peer, err);
+        }
+        drop(permit);
+    })
+}
+
+/// Run a viewer for one WebSocket until either side ends it
+async fn session(mut socket: WebSocket, shared: &Shared, peer: SocketAddr) -> Result<()> {
+    // The page reports its size as soon as it connects
+    let size = match time::timeout(SIZE_TIMEOUT, socket.recv()).await {
+        Ok(Some(Ok(Message::Text(text)))) => match serde_json::from_str(&text) {
+            Ok(ClientMessage::Resize { cols, rows }) => (cols.max(1), rows.max(1)),
+            Err(_) => DEFAULT_SIZE,
+        },
+        Ok(Some(Ok(_))) | Err(_) => DEFAULT_SIZE,
+        Ok(None | Some(Err(_))) => return Ok(()),
+    };
+    let mut running = shared.viewer.start(peer.to_string(), size, ColorDepth::TrueColor)?;
+
+    let mut decoder = Decoder::default();
+    let mut deadline = Instant::now() + shared.idle_timeout;
+    let close = loop {
+        tokio::select! {
+            frame = running.output.recv() => match frame {
+                Some(frame) => socket.send(Message::Binary(frame.into())).await?,
+                None => break Some((CLOSE_NORMAL, "Session ended")),
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Binary(bytes))) => {
+                    deadline = Instant::now() + shared.idle_timeout;
+                    for event in decoder.decode(&bytes) {
+                        let _ = running.input.send(event);
+                    }
+                }
+                Some(Ok(Message::Text(text))) => {
+                    if let Ok(ClientMessage::Resize { cols, rows }) = serde_json::from_str(&text) {
+                        let _ = running.input.send(Event::Resize(cols.max(1), rows.max(1)));
+                    }
+                }
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break None,
+                Some(Ok(_)) => {}
+            },
+            _ = time::sleep_until(deadline) => break Some((CLOSE_AWAY, "Closed after a while without input")),
+        }
+    };
+
+    // Let the viewer finish, so its last frames restore the terminal
+    let _ = running.input.send(Event::Terminate);
+    while let Some(frame) = running.output.recv().await {
+        let _ = socket.send(Message::Binary(frame.into())).await;
+    }
+    if let Some((code, reason)) = close {
+        let frame = CloseFrame { code, reason: reason.into() };
+        let _ = socket.send(Message::Close(Some(frame))).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serve::testing::{local_listener, Config};
+    use futures_util::{SinkExt, StreamExt};
+    use regex::Regex;
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+    use tokio_tungstenite::tungstenite::{self, Message as WsMessage};
+    use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    const YAML: &str = "name: Test Person\ndescription: About me\n";
+
+    /// Server on a free local port, with its address
+    async fn server(config: &Config, max_connections: usize, idle_timeout: Duration) -> SocketAddr {
+        let (listener, addr) = local_listener().await;
+        tokio::spawn(run(listener, config.viewer(), max_connections, idle_timeout));
+        addr
+    }
+
+    /// Connect and report a terminal of the given size
+    async fn connect(addr: SocketAddr, (cols, rows): (u16, u16)) -> Client {
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+        let resize = format!(r#"{{"type":"resize","cols":{},"rows":{}}}"#, cols, rows);
+        client.send(WsMessage::text(resize)).await.unwrap();
+        client
+    }
+
+    /// Next message from the server, failing after a few seconds
+    async fn next(client: &mut Client) -> Option<WsMessage> {
+        time::timeout(Duration::from_secs(5), client.next()).await.unwrap().map(Result::unwrap)
+    }
+
+    /// Read frames until one contains `text`
+    async fn wait_for(client: &mut Client, text: &str) {
+        let mut drawn = Vec::new();
+        while !String::from_utf8_lossy(&drawn).contains(text) {
+            match next(client).await {
+                Some(WsMessage::Binary(frame)) => drawn.extend_from_slice(&frame),
+                other => panic!("expected a frame, got {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn frames_follow_the_size_of_the_page() {
+        let config = Config::new("web-resize", YAML);
+        let addr = server(&config, 4, Duration::from_secs(60)).await;
+        let mut client = connect(addr, (60, 20)).await;
+        wait_for(&mut client, "About Me").await;
+
+        client.send(WsMessage::text(r#"{"type":"resize","cols":40,"rows":10}"#)).await.unwrap();
+        let Some(WsMessage::Binary(frame)) = next(&mut client).await else {
+            panic!("expected a frame after resizing");
+        };
+        // Cursor moves are 1-based `ESC [ row ; column H`
+        let frame = String::from_utf8_lossy(&frame);
+        let moves: Vec<(u16, u16)> = Regex::new(r"\x1b\[(\d+);(\d+)H")
+            .unwrap()
+            .captures_iter(&frame)
+            .map(|caps| (caps[1].parse().unwrap(), caps[2].parse().unwrap()))
+            .collect();
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|&(row, column)| row <= 10 && column <= 40), "{:?}", moves);
+    }
+
+    #[tokio::test]
+    async fn idle_connections_are_closed() {
+        let config = Config::new("web-idle", YAML);
+        let addr = server(&config, 4, Duration::from_millis(300)).await;
+        let mut client = connect(addr, (80, 24)).await;
+        wait_for(&mut client, "About Me").await;
+
+        loop {
+            match next(&mut client).await {
+                Some(WsMessage::Binary(_)) => {}
+                Some(WsMessage::Close(Some(frame))) => break assert_eq!(frame.code, CloseCode::Away),
+                other => panic!("expected the connection to close, got {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn visitors_past_the_limit_are_turned_away() {
+        let config = Config::new("web-limit", YAML);
+        let addr = server(&config, 1, Duration::from_secs(60)).await;
+        let mut first = connect(addr, (80, 24)).await;
+        wait_for(&mut first, "About Me").await;
+
+        match tokio_tungstenite::connect_async(format!("ws://{}/ws", addr)).await {
+            Err(tungstenite::Error::Http(response)) => assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE),
+            other => panic!("expected 503, got {:?}", other.map(|(_, response)| response)),
+        }
+    }
+}
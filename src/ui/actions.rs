@@ -0,0 +1,228 @@
+//! Action registry
+//!
+//! Every user-facing feature is an [`Action`]. Keys are bound to actions by
+//! the keymap and the command palette lists actions together with their keys.
+//! Prompts and popups have their own fixed commands.
+
+/// Something the user can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Go to the Home view
+    Home,
+    /// Go to the previous section
+    PreviousSection,
+    /// Go to the next section
+    NextSection,
+    /// Go to a section by navigation index (0 is Home)
+    GoToSection(usize),
+    /// Select the previous item in the current section
+    SelectPrevious,
+    /// Select the next item in the current section
+    SelectNext,
+    /// Show the selected item in full
+    OpenDetail,
+    /// Open the search prompt
+    Search,
+    /// Jump to the next search match
+    NextMatch,
+    /// Jump to the previous search match
+    PreviousMatch,
+    /// Open the selected link in the browser
+    OpenLink,
+    /// Copy the selected link to the clipboard
+    CopyLink,
+    /// Show the contact card or a link as a QR code
+    QrCode,
+    /// Export the portfolio to a file
+    Export,
+    /// Save the current view as an SVG image
+    Screenshot,
+    /// Reload the portfolio data
+    Reload,
+    /// Switch to the next color theme
+    ToggleTheme,
+    /// Open the command palette
+    CommandPalette,
+    /// Show the key binding help
+    Help,
+    /// Stop the application and return to the shell until resumed
+    Suspend,
+    /// Quit the application
+    Quit,
+}
+
+impl Action {
+    /// Actions listed in the command palette, in display order
+    pub const ALL: &'static [Action] = &[
+        Action::Home,
+        Action::PreviousSection,
+        Action::NextSection,
+        Action::SelectPrevious,
+        Action::SelectNext,
+        Action::OpenDetail,
+        Action::Search,
+        Action::NextMatch,
+        Action::PreviousMatch,
+        Action::OpenLink,
+        Action::CopyLink,
+        Action::QrCode,
+        Action::Export,
+        Action::Screenshot,
+        Action::Reload,
+        Action::ToggleTheme,
+        Action::CommandPalette,
+        Action::Help,
+        Action::Suspend,
+        Action::Quit,
+    ];
+
+    /// Human readable name
+    pub fn name(&self) -> String {
+        match self {
+            Action::Home => "Go to Home".to_string(),
+            Action::PreviousSection => "Previous section".to_string(),
+            Action::NextSection => "Next section".to_string(),
+            Action::GoToSection(index) => format!("Go to section {}", index),
+            Action::SelectPrevious => "Select previous item".to_string(),
+            Action::SelectNext => "Select next item".to_string(),
+            Action::OpenDetail => "Show item details".to_string(),
+            Action::Search => "Search".to_string(),
+            Action::NextMatch => "Next search match".to_string(),
+            Action::PreviousMatch => "Previous search match".to_string(),
+            Action::OpenLink => "Open link in browser".to_string(),
+            Action::CopyLink => "Copy link to clipboard".to_string(),
+            Action::QrCode => "Show QR code".to_string(),
+            Action::Export => "Export portfolio".to_string(),
+            Action::Screenshot => "Save a screenshot".to_string(),
+            Action::Reload => "Reload portfolio".to_string(),
+            Action::ToggleTheme => "Switch theme".to_string(),
+            Action::CommandPalette => "Command palette".to_string(),
+            Action::Help => "Key binding help".to_string(),
+            Action::Suspend => "Suspend to the shell".to_string(),
+            Action::Quit => "Quit".to_string(),
+        }
+    }
+
+    /// Identifier used for the action in the settings file
+    pub fn id(&self) -> String {
+        match self {
+            Action::Home => "home".to_string(),
+            Action::PreviousSection => "previous_section".to_string(),
+            Action::NextSection => "next_section".to_string(),
+            Action::GoToSection(index) => format!("section_{}", index),
+            Action::SelectPrevious => "select_previous".to_string(),
+            Action::SelectNext => "select_next".to_string(),
+            Action::OpenDetail => "open_detail".to_string(),
+            Action::Search => "search".to_string(),
+            Action::NextMatch => "next_match".to_string(),
+            Action::PreviousMatch => "previous_match".to_string(),
+            Action::OpenLink => "open_link".to_string(),
+            Action::CopyLink => "copy_link".to_string(),
+            Action::QrCode => "qr_code".to_string(),
+            Action::Export => "export".to_string(),
+            Action::Screenshot => "screenshot".to_string(),
+            Action::Reload => "reload".to_string(),
+            Action::ToggleTheme => "toggle_theme".to_string(),
+            Action::CommandPalette => "command_palette".to_string(),
+            Action::Help => "help".to_string(),
+            Action::Suspend => "suspend".to_string(),
+            Action::Quit => "quit".to_string(),
+        }
+    }
+
+    /// Context the action applies to
+    pub fn context(&self) -> Context {
+        match self {
+            Action::SelectPrevious
+            | Action::SelectNext
+            | Action::OpenDetail
+            | Action::OpenLink
+            | Action::CopyLink => Context::List,
+            _ => Context::Global,
+        }
+    }
+
+    /// Look up an action by its settings identifier
+    pub fn from_id(id: &str) -> Option<Action> {
+        if let Some(index) = id.strip_prefix("section_") {
+            return index.parse().ok().map(Action::GoToSection);
+        }
+        Action::ALL.iter().copied().find(|action| action.id() == id)
+    }
+}
+
+/// Where a key binding applies, used to group bindings in the help overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// Anywhere outside of prompts and popups
+    Global,
+    /// Content sections with selectable items
+    List,
+    /// Search and command palette prompts
+    Prompt,
+    /// Item details, help and QR code popups
+    Popup,
+    /// Screen shown when loading failed
+    Error,
+}
+
+impl Context {
+    /// All contexts in help display order
+    pub const ALL: &'static [Context] = &[Context::Global, Context::List, Context::Prompt, Context::Popup, Context::Error];
+
+    /// Heading shown in the help overlay
+    pub fn title(&self) -> &'static str {
+        match self {
+            Context::Global => "Global",
+            Context::List => "List",
+            Context::Prompt => "Search and commands",
+            Context::Popup => "Popups",
+            Context::Error => "Error screen",
+        }
+    }
+}
+
+/// Commands available while typing into a prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptCommand {
+    /// Close the prompt without acting
+    Cancel,
+    /// Jump to the selected match or run the selected command
+    Accept,
+    /// Select the previous entry
+    Previous,
+    /// Select the next entry
+    Next,
+    /// Delete the last character of the query
+    DeleteChar,
+}
+
+/// Commands available on the error screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCommand {
+    /// Load the same source again
+    Retry,
+    /// Enter a different file or URL to load
+    SwitchSource,
+    /// Show the data cached from the last successful load
+    UseCache,
+}
+
+/// Commands available while a popup is open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupCommand {
+    /// Close the popup
+    Close,
+    /// Scroll up one line
+    ScrollUp,
+    /// Scroll down one line
+    ScrollDown,
+    /// Scroll up one page
+    PageUp,
+    /// Scroll down one page
+    PageDown,
+    /// Show the previous QR code
+    Previous,
+    /// Show the next QR code
+    Next,
+}
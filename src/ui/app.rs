@@ -1,24 +1,48 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use crossterm::ExecutableCommand;
-use ratatui::backend::CrosstermBackend;
-use ratatui::layout::Alignment;
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::widgets::{Block, Borders, Paragraph};
-use ratatui::Terminal;
-use std::io::{self, Stdout};
-use std::time::{Duration, Instant};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders};
+use ratatui::{Frame, Terminal};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::task::JoinHandle;
+use tokio::time::{self, MissedTickBehavior};
 
+use crate::config::args::ScreenshotFormat;
 use crate::config::Args;
+use crate::data::cache::Cache;
+use crate::data::error::LoadError;
 use crate::data::{fetcher::Fetcher, parser::Parser, Portfolio};
+use crate::export::pdf::PdfOptions;
+use crate::export::{self, absolute_url, vcard};
 use crate::processor::Formatter;
+use crate::processor::formatter::{FormattedContentItem, TextCache};
+use crate::serve::text;
+use crate::ui::actions::{Action, ErrorCommand, PopupCommand, PromptCommand};
+use crate::ui::events::{Event, EventHandler};
+use crate::ui::icons::{IconMode, Icons};
+use crate::ui::keymap::{self, Keymap, ERROR_KEYS, POPUP_KEYS, PROMPT_KEYS};
+use crate::ui::mouse::{HitMap, HitTarget};
+use crate::ui::palette::{Palette, PaletteEntry};
+use crate::ui::screenshot;
+use crate::ui::search::{Search, SearchIndex, SearchTarget};
+use crate::ui::terminal::TerminalSession;
+use crate::ui::theme::{Theme, ThemeSet};
+use crate::ui::views::content::Selection;
+use crate::ui::views::qr::QrTarget;
+use crate::ui::views::tabs::TabMark;
 use crate::ui::views::{self, View};
 
 /// Application state
 pub struct App {
-    /// Terminal
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// Terminal, restored when the application is dropped; `None` when headless
+    terminal: Option<TerminalSession>,
     /// Portfolio data
     portfolio: Option<Portfolio>,
     /// Formatted portfolio data
@@ -29,98 +53,390 @@ pub struct App {
     should_quit: bool,
     /// Config path
     config_path: String,
+    /// Copy of the source shared by the viewers of a server, loaded through
+    /// instead of fetching the source for each of them
+    shared_source: Option<Arc<text::Cache<String>>>,
     /// Available content sections
     content_sections: Vec<String>,
     /// Current section index
     current_section_index: usize,
+    /// Icon resolver
+    icons: Icons,
+    /// Read state of each content section
+    section_marks: Vec<TabMark>,
+    /// Content fingerprints of the last load, by section index and title
+    section_fingerprints: HashMap<(usize, String), u64>,
+    /// First visible tab in the tab bar
+    tab_offset: usize,
+    /// Clickable regions from the last render
+    hits: HitMap,
+    /// Reload the data on the next loop iteration
+    reload_requested: bool,
+    /// Cancel the running load on the next loop iteration
+    cancel_requested: bool,
+    /// Suspend to the shell on the next loop iteration
+    suspend_requested: bool,
+    /// Load running in the background, if any
+    loading: Option<Loading>,
+    /// Why the last load failed, when there is no data to show
+    load_error: Option<LoadError>,
+    /// When the cached copy of the source was saved, looked up when a load
+    /// fails
+    cache_saved: Option<SystemTime>,
+    /// Source typed into the switch source prompt, while it is open
+    source_prompt: Option<String>,
+    /// File name typed into the export prompt, while it is open
+    export_prompt: Option<String>,
+    /// Selected item in the current content section
+    selection: Selection,
+    /// Search index over the formatted portfolio
+    search_index: SearchIndex,
+    /// Search prompt and results
+    search: Search,
+    /// Command palette
+    palette: Palette,
+    /// Status message from the last action
+    status: Option<String>,
+    /// Available color themes
+    themes: ThemeSet,
+    /// Active color theme
+    theme: Theme,
+    /// Theme was chosen on the command line and overrides the portfolio's darkmode
+    theme_pinned: bool,
+    /// Active key bindings
+    keymap: Keymap,
+    /// Open popup, if any
+    popup: Option<Popup>,
+    /// Scroll position of the open popup
+    popup_scroll: u16,
+    /// Codes the QR code popup switches between
+    qr_targets: Vec<QrTarget>,
+    /// Code shown in the QR code popup
+    qr_index: usize,
+    /// Formatted text reused between frames
+    text_cache: TextCache,
+    /// The screen is out of date and must be redrawn
+    dirty: bool,
 }
 
+/// Progress of a background load
+#[derive(Debug, Clone)]
+struct Loading {
+    /// Source being loaded
+    source: String,
+    /// When the load started
+    started: Instant,
+    /// Spinner frame, advanced on every tick
+    spinner: usize,
+}
+
+/// Spinner frames for the loading indicator
+const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Spinner frames for the loading indicator in ASCII mode
+const ASCII_SPINNER: &[&str] = &["|", "/", "-", "\\"];
+
+/// Popups shown on top of the current view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Popup {
+    /// Key binding help
+    Help,
+    /// Details of the selected item
+    Detail,
+    /// Contact card or a link as a QR code
+    Qr,
+}
+
+/// File name the export prompt starts with
+const DEFAULT_EXPORT: &str = "portfolio.md";
+
+/// Lines scrolled by a page up or down in a popup
+const POPUP_PAGE: u16 = 10;
+
+/// Interval between animation ticks
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Lines scrolled by one mouse wheel step in a popup
+const WHEEL_LINES: u16 = 3;
+
 impl App {
-    /// Create a new application
-    pub fn new(args: Args) -> Result<Self> {
-        // Setup terminal
-        enable_raw_mode()?;
-        io::stdout().execute(EnterAlternateScreen)?;
-        let backend = CrosstermBackend::new(io::stdout());
-        let terminal = Terminal::new(backend)?;
+    /// Create a new application, taking over the terminal
+    pub fn new(args: Args, keymap: Keymap, themes: ThemeSet) -> Result<Self> {
+        let mouse = !args.no_mouse;
+        let mut app = Self::headless(args, keymap, themes)?;
+        app.terminal = Some(TerminalSession::enter(mouse)?);
+        Ok(app)
+    }
+
+    /// Create an application drawing on a remote terminal
+    ///
+    /// Actions that would act on this machine, like opening links or
+    /// writing files, are turned off. The source is loaded through `source`,
+    /// which the other viewers of the server share.
+    pub fn remote(
+        args: Args,
+        keymap: Keymap,
+        themes: ThemeSet,
+        terminal: TerminalSession,
+        source: Arc<text::Cache<String>>,
+    ) -> Result<Self> {
+        let mut app = Self::headless(args, keymap, themes)?;
+        app.terminal = Some(terminal);
+        app.shared_source = Some(source);
+        Ok(app)
+    }
+
+    /// Create an application that only renders off-screen, for screenshots
+    pub fn headless(args: Args, keymap: Keymap, themes: ThemeSet) -> Result<Self> {
+        let theme = match args.theme {
+            Some(ref name) => themes.get(name)?.clone(),
+            None => themes.for_darkmode(true).clone(),
+        };
 
         Ok(Self {
-            terminal,
+            terminal: None,
             portfolio: None,
             formatted_portfolio: None,
             current_view: View::Home,
             should_quit: false,
             config_path: args.config_path,
+            shared_source: None,
             content_sections: Vec::new(),
             current_section_index: 0,
+            icons: Icons::new(IconMode::from_flags(args.ascii, args.nerd_fonts)),
+            section_marks: Vec::new(),
+            section_fingerprints: HashMap::new(),
+            tab_offset: 0,
+            hits: HitMap::default(),
+            reload_requested: false,
+            cancel_requested: false,
+            suspend_requested: false,
+            loading: None,
+            load_error: None,
+            cache_saved: None,
+            source_prompt: None,
+            export_prompt: None,
+            selection: Selection::default(),
+            search_index: SearchIndex::default(),
+            search: Search::default(),
+            palette: Palette::default(),
+            status: None,
+            theme_pinned: args.theme.is_some(),
+            themes,
+            theme,
+            keymap,
+            popup: None,
+            popup_scroll: 0,
+            qr_targets: Vec::new(),
+            qr_index: 0,
+            text_cache: TextCache::default(),
+            dirty: true,
         })
     }
 
     /// Run the application
     pub async fn run(&mut self) -> Result<()> {
-        // Load data
-        self.load_data().await?;
+        self.run_with(EventHandler::new()).await
+    }
 
-        // Main loop
-        let tick_rate = Duration::from_millis(250);
-        let mut last_tick = Instant::now();
+    /// Run the application on events from `events`
+    pub async fn run_with(&mut self, mut events: EventHandler) -> Result<()> {
+        // Load data in the background while the progress screen is shown
+        let mut load_task = Some(self.start_loading());
 
+        // Ticks only run while something animates, so an idle app sleeps
+        let mut ticks = time::interval(TICK_RATE);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // Main loop: wait for an event and redraw only if it changed something
         while !self.should_quit {
-            // Handle events
-            let timeout = tick_rate
-                .checked_sub(last_tick.elapsed())
-                .unwrap_or_else(|| Duration::from_secs(0));
-
-            if event::poll(timeout)? {
-                if let Event::Key(key) = event::read()? {
-                    self.handle_key(key.code);
-                }
+            if self.dirty {
+                self.render()?;
+                self.dirty = false;
             }
 
-            if last_tick.elapsed() >= tick_rate {
-                self.tick();
-                last_tick = Instant::now();
+            tokio::select! {
+                event = events.next() => match event {
+                    Some(event) => self.handle_event(event)?,
+                    None => break,
+                },
+                _ = ticks.tick(), if self.loading.is_some() => self.handle_event(Event::Tick)?,
+                result = async { load_task.as_mut().expect("guarded by is_some").await }, if load_task.is_some() => {
+                    load_task = None;
+                    self.finish_loading(result.unwrap_or_else(|err| Err(err.into())));
+                },
             }
 
-            // Render
-            self.render()?;
+            if self.suspend_requested {
+                self.suspend_requested = false;
+                if let Some(ref mut terminal) = self.terminal {
+                    terminal.suspend()?;
+                }
+                self.dirty = true;
+            }
+            if self.cancel_requested {
+                self.cancel_requested = false;
+                if let Some(task) = load_task.take() {
+                    task.abort();
+                    self.cancel_loading();
+                }
+            }
+            if self.reload_requested {
+                self.reload_requested = false;
+                if let Some(task) = load_task.take() {
+                    task.abort();
+                }
+                load_task = Some(self.start_loading());
+                ticks.reset();
+            }
         }
 
-        // Restore terminal
-        disable_raw_mode()?;
-        io::stdout().execute(LeaveAlternateScreen)?;
+        Ok(())
+    }
 
+    /// Apply one event, setting the dirty flag if the screen changed
+    fn handle_event(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                self.handle_key(key);
+                self.dirty = true;
+            }
+            Event::Key(_) => {}
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            Event::Resize(width, height) => {
+                if let Some(ref mut terminal) = self.terminal {
+                    terminal.resize(width, height)?;
+                }
+                self.dirty = true;
+            }
+            Event::Tick => self.tick(),
+            Event::Suspend => self.suspend_requested = true,
+            Event::Terminate => self.should_quit = true,
+        }
         Ok(())
     }
 
-    /// Load data from source
-    async fn load_data(&mut self) -> Result<()> {
-        // Create fetcher, parser, and formatter
-        let fetcher = Fetcher::new();
-        let parser = Parser::new();
-        let formatter = Formatter::new();
+    /// Start loading data from the source on a background task
+    fn start_loading(&mut self) -> JoinHandle<Result<String>> {
+        self.loading = Some(Loading {
+            source: self.config_path.clone(),
+            started: Instant::now(),
+            spinner: 0,
+        });
+        self.load_error = None;
+        self.dirty = true;
         
-        // Fetch data from source
-        let content = fetcher.fetch(&self.config_path).await?;
-        
-        // Parse data
-        let portfolio = parser.parse(&content)?;
+        let source = self.config_path.clone();
+        let shared = self.shared_source.clone();
+        tokio::spawn(async move {
+            match shared {
+                Some(shared) => Ok(String::clone(&*shared.get(|| async { Fetcher::new().fetch(&source).await }).await?)),
+                None => Fetcher::new().fetch(&source).await,
+            }
+        })
+    }
+    
+    /// Apply the result of a background load
+    ///
+    /// Data that parses is saved to the cache for later fallback.
+    fn finish_loading(&mut self, result: Result<String>) {
+        let Some(loading) = self.loading.take() else {
+            return;
+        };
+        self.dirty = true;
+        let result = match result {
+            Ok(content) => match Parser::new().parse(&content) {
+                Ok(portfolio) => {
+                    // A cache that cannot be written only loses the fallback
+                    let _ = Cache::new().store(&loading.source, &content);
+                    Ok(portfolio)
+                }
+                Err(err) => Err(LoadError::from_parse(&err, &content)),
+            },
+            Err(err) => Err(LoadError::from_fetch(&err, &loading.source)),
+        };
+        match result {
+            Ok(portfolio) => self.load_data(portfolio),
+            Err(error) => self.load_failed(error),
+        }
+    }
+    
+    /// Stop waiting for a background load
+    fn cancel_loading(&mut self) {
+        self.loading = None;
+        self.load_failed(LoadError::Cancelled);
+    }
+    
+    /// Show why a load failed
+    ///
+    /// A failed reload keeps the previous data and only reports the error.
+    fn load_failed(&mut self, error: LoadError) {
+        self.dirty = true;
+        if self.portfolio.is_some() {
+            self.status = Some(match error {
+                LoadError::Cancelled => "Reload cancelled".to_string(),
+                error => format!("Reload failed: {}", error.title()),
+            });
+        } else {
+            self.load_error = Some(error);
+            self.cache_saved = Cache::new().saved(&self.config_path);
+        }
+    }
+    
+    /// Show the cached copy of the current source
+    fn load_cached(&mut self) {
+        let Some(cached) = Cache::new().load(&self.config_path) else {
+            self.status = Some("No cached data for this source".to_string());
+            return;
+        };
+        match Parser::new().parse(&cached.content) {
+            Ok(portfolio) => {
+                self.load_error = None;
+                self.load_data(portfolio);
+                let age = SystemTime::now().duration_since(cached.saved).unwrap_or_default();
+                self.status = Some(format!("Showing cached data saved {}", views::error::format_age(age)));
+            }
+            Err(err) => {
+                self.load_error = Some(LoadError::from_parse(&err, &cached.content));
+                self.cache_saved = Some(cached.saved);
+            }
+        }
+    }
+    
+    /// Store freshly loaded data
+    fn load_data(&mut self, portfolio: Portfolio) {
+        let formatter = Formatter::new();
         
         // Store the portfolio data
+        if self.portfolio.is_none() && !self.theme_pinned {
+            self.theme = self.themes.for_darkmode(portfolio.darkmode.unwrap_or(true)).clone();
+        }
         self.portfolio = Some(portfolio.clone());
         
         // Format the portfolio data for display
-        self.formatted_portfolio = Some(formatter.format(&portfolio));
+        let formatted_portfolio = formatter.format(&portfolio);
+        self.search_index = SearchIndex::new(&formatted_portfolio);
+        self.formatted_portfolio = Some(formatted_portfolio);
+        self.dirty = true;
         
         // Extract content sections for navigation
         self.extract_content_sections();
-        
-        Ok(())
     }
     
     /// Extract content sections for navigation
     fn extract_content_sections(&mut self) {
         if let Some(ref formatted_portfolio) = self.formatted_portfolio {
+            // Remember the read state from the previous load, by index and
+            // title so sections sharing a title keep their own state
+            let previous_marks: HashMap<(usize, String), TabMark> = self
+                .content_sections
+                .iter()
+                .skip(1)
+                .cloned()
+                .enumerate()
+                .zip(self.section_marks.iter().skip(1).copied())
+                .collect();
+            
             // Always include Home as the first section
             self.content_sections = vec!["Home".to_string()];
             
@@ -128,42 +444,564 @@ impl App {
             for section in &formatted_portfolio.content_sections {
                 self.content_sections.push(section.title.clone());
             }
+            
+            // Work out which sections are new or changed since the last load
+            let first_load = self.section_fingerprints.is_empty();
+            let mut fingerprints = HashMap::new();
+            let mut marks = vec![TabMark::Seen];
+            for (index, section) in formatted_portfolio.content_sections.iter().enumerate() {
+                let key = (index, section.title.clone());
+                let mut hasher = DefaultHasher::new();
+                format!("{:?}", section).hash(&mut hasher);
+                let fingerprint = hasher.finish();
+                
+                let mark = if first_load {
+                    TabMark::Unread
+                } else if self.section_fingerprints.get(&key) != Some(&fingerprint) {
+                    TabMark::Changed
+                } else {
+                    previous_marks.get(&key).copied().unwrap_or(TabMark::Seen)
+                };
+                marks.push(mark);
+                fingerprints.insert(key, fingerprint);
+            }
+            self.section_fingerprints = fingerprints;
+            self.section_marks = marks;
+            
+            // Keep the current section in range after a reload
+            if self.current_section_index >= self.content_sections.len() {
+                self.current_section_index = 0;
+            }
+            self.update_view_from_section();
         }
     }
 
     /// Handle key events
-    fn handle_key(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char('q') => self.should_quit = true,
-            KeyCode::Char('h') => {
-                self.current_section_index = 0; // Home is always the first section
-                self.current_view = View::Home;
-            },
-            KeyCode::Left | KeyCode::Char('j') => self.previous_section(),
-            KeyCode::Right | KeyCode::Char('l') => self.next_section(),
-            KeyCode::Char(c) => {
-                // Handle numeric keys for direct section navigation
-                if let Some(digit) = c.to_digit(10) {
-                    let index = if digit == 0 { 9 } else { (digit - 1) as usize };
-                    if index < self.content_sections.len() {
-                        self.navigate_to_section(index);
-                    }
+    fn handle_key(&mut self, key: KeyEvent) {
+        // Suspending works everywhere, like Ctrl-Z in a shell
+        if self.keymap.action_for(&key) == Some(Action::Suspend) {
+            self.perform(Action::Suspend);
+            return;
+        }
+        // Escape cancels a running load
+        if self.loading.is_some() && keymap::prompt_command(key.code) == Some(PromptCommand::Cancel) {
+            self.cancel_requested = true;
+            return;
+        }
+        if self.source_prompt.is_some() {
+            self.handle_source_key(key.code);
+            return;
+        }
+        if self.export_prompt.is_some() {
+            self.handle_export_key(key.code);
+            return;
+        }
+        if self.formatted_portfolio.is_none() {
+            self.handle_error_key(key);
+            return;
+        }
+        if self.popup.is_some() {
+            self.handle_popup_key(key);
+            return;
+        }
+        if self.palette.active {
+            self.handle_palette_key(key.code);
+            return;
+        }
+        if self.search.active {
+            self.handle_search_key(key.code);
+            return;
+        }
+        
+        if let Some(action) = self.keymap.action_for(&key) {
+            self.perform(action);
+        } else if let KeyCode::Char(c) = key.code {
+            // Unbound letters jump to the first section starting with that letter
+            let c = c.to_lowercase().next().unwrap_or(c);
+            if let Some(i) = self.content_sections.iter().position(|section| section.to_lowercase().starts_with(c)) {
+                self.navigate_to_section(i);
+            }
+        }
+    }
+    
+    /// Perform an action
+    fn perform(&mut self, action: Action) {
+        self.status = None;
+        if self.is_remote() && matches!(action, Action::Export | Action::Screenshot | Action::Suspend) {
+            self.status = Some(format!("{} is not available over a remote connection", action.name()));
+            return;
+        }
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::Home => self.navigate_to_section(0), // Home is always the first section
+            Action::PreviousSection => self.previous_section(),
+            Action::NextSection => self.next_section(),
+            Action::GoToSection(index) => self.navigate_to_section(index),
+            Action::SelectPrevious => self.select_item(-1),
+            Action::SelectNext => self.select_item(1),
+            Action::Search => self.search.open(),
+            Action::NextMatch | Action::PreviousMatch => {
+                if self.search.results.is_empty() {
+                    self.status = Some("No search matches".to_string());
                 } else {
-                    // Handle first letter navigation (except for reserved keys)
-                    if c != 'q' && c != 'h' && c != 'j' && c != 'l' {
-                        for (i, section) in self.content_sections.iter().enumerate() {
-                            if !section.is_empty() && section.to_lowercase().starts_with(c.to_lowercase().next().unwrap()) {
-                                self.navigate_to_section(i);
-                                break;
-                            }
-                        }
-                    }
+                    self.search.select_relative(if action == Action::NextMatch { 1 } else { -1 });
+                    self.jump_to_search_result();
+                }
+            },
+            Action::OpenLink => match self.current_link() {
+                Some(url) => self.open_url(&url),
+                None => self.status = Some("No link here".to_string()),
+            },
+            Action::CopyLink => {
+                self.status = Some(match self.current_link() {
+                    Some(url) => match self.copy_to_clipboard(&url) {
+                        Ok(()) => format!("Copied {}", url),
+                        Err(err) => format!("Could not copy {}: {}", url, err),
+                    },
+                    None => "No link here".to_string(),
+                });
+            },
+            Action::QrCode => self.open_qr_code(),
+            Action::Export if self.portfolio.is_some() => self.export_prompt = Some(DEFAULT_EXPORT.to_string()),
+            Action::Export => self.status = Some("Nothing to export".to_string()),
+            Action::Screenshot => self.status = Some(self.save_screenshot()),
+            Action::Reload => self.reload_requested = true,
+            Action::ToggleTheme => {
+                self.theme = self.themes.next_after(&self.theme.name).clone();
+                self.status = Some(format!("Theme: {}", self.theme.name));
+            },
+            Action::CommandPalette => self.palette.open(self.palette_entries()),
+            Action::Help => self.open_popup(Popup::Help),
+            Action::Suspend => self.suspend_requested = true,
+            Action::OpenDetail => {
+                if self.selected_content_item().is_some() {
+                    self.open_popup(Popup::Detail);
+                }
+            },
+        }
+    }
+    
+    /// Handle key events on the error screen
+    fn handle_error_key(&mut self, key: KeyEvent) {
+        self.status = None;
+        match keymap::error_command(key.code) {
+            Some(ErrorCommand::Retry) => self.reload_requested = true,
+            Some(ErrorCommand::SwitchSource) if !self.is_remote() => self.source_prompt = Some(self.config_path.clone()),
+            Some(ErrorCommand::SwitchSource) => {},
+            Some(ErrorCommand::UseCache) => self.load_cached(),
+            None => {
+                if self.keymap.action_for(&key) == Some(Action::Quit) {
+                    self.should_quit = true;
+                }
+            }
+        }
+    }
+    
+    /// Handle key events while the switch source prompt is open
+    fn handle_source_key(&mut self, key: KeyCode) {
+        let Some(ref mut input) = self.source_prompt else {
+            return;
+        };
+        match (keymap::prompt_command(key), key) {
+            (Some(PromptCommand::Cancel), _) => self.source_prompt = None,
+            (Some(PromptCommand::Accept), _) => {
+                let source = input.trim().to_string();
+                self.source_prompt = None;
+                if !source.is_empty() {
+                    self.config_path = source;
+                    self.reload_requested = true;
+                }
+            },
+            (Some(PromptCommand::DeleteChar), _) => {
+                input.pop();
+            },
+            (None, KeyCode::Char(c)) => input.push(c),
+            _ => {}
+        }
+    }
+    
+    /// Handle key events while the export prompt is open
+    fn handle_export_key(&mut self, key: KeyCode) {
+        let Some(ref mut input) = self.export_prompt else {
+            return;
+        };
+        match (keymap::prompt_command(key), key) {
+            (Some(PromptCommand::Cancel), _) => self.export_prompt = None,
+            (Some(PromptCommand::Accept), _) => {
+                let path = input.trim().to_string();
+                self.export_prompt = None;
+                if !path.is_empty() {
+                    self.status = Some(self.export(&path));
                 }
             },
+            (Some(PromptCommand::DeleteChar), _) => {
+                input.pop();
+            },
+            (None, KeyCode::Char(c)) => input.push(c),
             _ => {}
         }
     }
     
+    /// Handle key events while the command palette is open
+    fn handle_palette_key(&mut self, key: KeyCode) {
+        match (keymap::prompt_command(key), key) {
+            (Some(PromptCommand::Cancel), _) => self.palette.close(),
+            (Some(PromptCommand::Accept), _) => {
+                let action = self.palette.current();
+                self.palette.close();
+                if let Some(action) = action {
+                    self.perform(action);
+                }
+            },
+            (Some(PromptCommand::Previous), _) => self.palette.select_relative(-1),
+            (Some(PromptCommand::Next), _) => self.palette.select_relative(1),
+            (Some(PromptCommand::DeleteChar), _) => {
+                self.palette.query.pop();
+                self.palette.filter();
+            },
+            (None, KeyCode::Char(c)) => {
+                self.palette.query.push(c);
+                self.palette.filter();
+            },
+            _ => {}
+        }
+    }
+    
+    /// Handle key events while a popup is open
+    fn handle_popup_key(&mut self, key: KeyEvent) {
+        match keymap::popup_command(key.code) {
+            Some(PopupCommand::Close) => self.popup = None,
+            Some(PopupCommand::ScrollUp) => self.popup_scroll = self.popup_scroll.saturating_sub(1),
+            Some(PopupCommand::ScrollDown) => self.popup_scroll = self.popup_scroll.saturating_add(1),
+            Some(PopupCommand::PageUp) => self.popup_scroll = self.popup_scroll.saturating_sub(POPUP_PAGE),
+            Some(PopupCommand::PageDown) => self.popup_scroll = self.popup_scroll.saturating_add(POPUP_PAGE),
+            Some(PopupCommand::Previous | PopupCommand::Next) if self.popup == Some(Popup::Qr) => {
+                let count = self.qr_targets.len().max(1);
+                let step = if keymap::popup_command(key.code) == Some(PopupCommand::Next) { 1 } else { count - 1 };
+                self.qr_index = (self.qr_index + step) % count;
+            }
+            Some(PopupCommand::Previous | PopupCommand::Next) => {}
+            None => {
+                // Links can still be opened or copied from the details popup,
+                // and popups can be captured in screenshots
+                if let Some(action @ (Action::OpenLink | Action::CopyLink | Action::Screenshot | Action::Quit)) = self.keymap.action_for(&key) {
+                    self.perform(action);
+                }
+            }
+        }
+    }
+    
+    /// Open a popup scrolled to the top
+    fn open_popup(&mut self, popup: Popup) {
+        self.popup = Some(popup);
+        self.popup_scroll = 0;
+    }
+    
+    /// Open the QR code popup
+    ///
+    /// The contact card comes first, then the selected item's link and every
+    /// contact link. The selected link is shown first when there is one.
+    fn open_qr_code(&mut self) {
+        let (Some(portfolio), Some(formatted)) = (&self.portfolio, &self.formatted_portfolio) else {
+            return;
+        };
+        let mut targets = vec![QrTarget {
+            label: "Contact card".to_string(),
+            data: vcard::render(portfolio, formatted),
+            caption: format!("vCard for {}", formatted.name),
+        }];
+        let selected = self.selected_content_item().zip(self.current_link());
+        let index = usize::from(selected.is_some());
+        if let Some((item, url)) = selected {
+            let label = if item.title.is_empty() { "Selected link".to_string() } else { item.title.clone() };
+            targets.push(QrTarget { label, data: url.clone(), caption: url });
+        }
+        targets.extend(formatted.social.iter().map(|social| QrTarget {
+            label: social.platform.clone(),
+            data: social.url.clone(),
+            caption: social.username.clone(),
+        }));
+        targets.extend(formatted.links.iter().map(|link| QrTarget {
+            label: link.title.clone(),
+            data: absolute_url(&link.url),
+            caption: absolute_url(&link.url),
+        }));
+
+        self.qr_index = index;
+        self.qr_targets = targets;
+        self.open_popup(Popup::Qr);
+    }
+    
+    /// Build the command palette entries, including one per section
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let mut entries: Vec<PaletteEntry> = Action::ALL
+            .iter()
+            .map(|&action| PaletteEntry {
+                action,
+                name: action.name(),
+                keys: self.keymap.keys_for(action),
+            })
+            .collect();
+        for (i, section) in self.content_sections.iter().enumerate().skip(1) {
+            let action = Action::GoToSection(i);
+            entries.push(PaletteEntry {
+                action,
+                name: format!("Go to {}", section),
+                keys: self.keymap.keys_for(action),
+            });
+        }
+        entries
+    }
+    
+    /// Selected item of the current content section
+    fn selected_content_item(&self) -> Option<&FormattedContentItem> {
+        let View::Content(index) = self.current_view else {
+            return None;
+        };
+        self.formatted_portfolio.as_ref()?.content_sections.get(index)?.items.get(self.selection.selected)
+    }
+    
+    /// Key hints for the status line in the current context
+    fn status_hints(&self) -> Line<'static> {
+        if self.loading.is_some() {
+            let mut entries = vec![("Esc".to_string(), "cancel")];
+            entries.extend(self.keymap.key_hint(Action::Quit).map(|keys| (keys, "quit")));
+            return views::hint_line(entries, &self.theme);
+        }
+        if self.popup.is_some() {
+            let mut entries = keymap::group_keys(POPUP_KEYS);
+            if self.popup != Some(Popup::Qr) {
+                entries.retain(|&(_, description)| !description.contains("QR code"));
+            }
+            return views::hint_line(entries, &self.theme);
+        }
+        if self.search.active || self.palette.active || self.source_prompt.is_some() || self.export_prompt.is_some() {
+            return views::hint_line(keymap::group_keys(PROMPT_KEYS), &self.theme);
+        }
+        if self.formatted_portfolio.is_none() {
+            let mut entries = keymap::group_keys(ERROR_KEYS);
+            if self.cache_saved.is_none() {
+                entries.retain(|&(_, description)| description != "Use cached data");
+            }
+            if self.is_remote() {
+                entries.retain(|&(_, description)| description != "Switch source");
+            }
+            entries.extend(self.keymap.key_hint(Action::Quit).map(|keys| (keys, "quit")));
+            return views::hint_line(entries, &self.theme);
+        }
+        let entries: &[(Action, &'static str)] = match self.current_view {
+            View::Home => &[
+                (Action::NextSection, "sections"),
+                (Action::Search, "search"),
+                (Action::CommandPalette, "commands"),
+                (Action::Help, "help"),
+                (Action::Quit, "quit"),
+            ],
+            View::Content(_) => &[
+                (Action::SelectNext, "next"),
+                (Action::SelectPrevious, "prev"),
+                (Action::OpenDetail, "details"),
+                (Action::OpenLink, "open"),
+                (Action::NextSection, "sections"),
+                (Action::Help, "help"),
+                (Action::Quit, "quit"),
+            ],
+        };
+        views::help_line(&self.keymap, entries, &self.theme)
+    }
+    
+    /// Link for the current view or selected item
+    fn current_link(&self) -> Option<String> {
+        let portfolio = self.formatted_portfolio.as_ref()?;
+        let url = match self.current_view {
+            View::Home => portfolio
+                .social
+                .iter()
+                .find(|s| s.platform == "Website")
+                .or_else(|| portfolio.social.first())
+                .map(|s| s.url.clone()),
+            View::Content(index) => {
+                let item = portfolio.content_sections.get(index)?.items.get(self.selection.selected)?;
+                [&item.url, &item.link]
+                    .into_iter()
+                    .find(|url| !url.is_empty())
+                    .cloned()
+                    .or_else(|| item.links.first().map(|link| link.url.clone()))
+            }
+        }?;
+        
+        // Theme links are often written without a scheme
+        if url.contains("://") || url.starts_with("mailto:") {
+            Some(url)
+        } else {
+            Some(format!("https://{}", url))
+        }
+    }
+    
+    /// Export the loaded portfolio to a file in the format its extension names
+    fn export(&self, path: &str) -> String {
+        let Some(ref portfolio) = self.portfolio else {
+            return "Nothing to export".to_string();
+        };
+        let Some(format) = export::format_for_path(path) else {
+            return format!("Unknown format for {}, use .md, .html, .pdf, .json, .resume.json, .vcf or .gmi", path);
+        };
+        let result = export::render(portfolio, format, PdfOptions::default()).and_then(|output| Ok(std::fs::write(path, output)?));
+        match result {
+            Ok(()) => format!("Exported to {}", path),
+            Err(err) => format!("Export failed: {}", err),
+        }
+    }
+    
+    /// Save the screen as an SVG image named after the current section
+    fn save_screenshot(&mut self) -> String {
+        let section = self.content_sections.get(self.current_section_index).map_or("home", String::as_str);
+        let slug: String = section
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let path = format!("screenshot-{}.svg", if slug.is_empty() { "view" } else { &slug });
+        let result = crossterm::terminal::size()
+            .map_err(anyhow::Error::from)
+            .and_then(|(width, height)| self.screenshot(width, height))
+            .map(|buffer| screenshot::render(&buffer, ScreenshotFormat::Svg, &self.theme))
+            .and_then(|svg| Ok(std::fs::write(&path, svg)?));
+        match result {
+            Ok(()) => format!("Saved screenshot to {}", path),
+            Err(err) => format!("Screenshot failed: {}", err),
+        }
+    }
+    
+    /// Handle key events while the search prompt is open
+    fn handle_search_key(&mut self, key: KeyCode) {
+        match (keymap::prompt_command(key), key) {
+            (Some(PromptCommand::Cancel), _) => {
+                self.search.active = false;
+                self.search.results.clear();
+            },
+            (Some(PromptCommand::Accept), _) => {
+                self.search.active = false;
+                self.jump_to_search_result();
+            },
+            (Some(PromptCommand::Previous), _) => self.search.select_relative(-1),
+            (Some(PromptCommand::Next), _) => self.search.select_relative(1),
+            (Some(PromptCommand::DeleteChar), _) => {
+                self.search.query.pop();
+                self.search.update(&self.search_index);
+            },
+            (None, KeyCode::Char(c)) => {
+                self.search.query.push(c);
+                self.search.update(&self.search_index);
+            },
+            _ => {}
+        }
+    }
+    
+    /// Jump to the selected search result
+    fn jump_to_search_result(&mut self) {
+        if let Some(SearchTarget { section, item }) = self.search.current().map(|result| result.target) {
+            self.navigate_to_section(section);
+            self.selection.selected = item.unwrap_or(0);
+        }
+    }
+    
+    /// Move the item selection in the current content section
+    fn select_item(&mut self, delta: isize) {
+        let len = match (self.current_view, &self.formatted_portfolio) {
+            (View::Content(index), Some(portfolio)) => portfolio.content_sections.get(index).map_or(0, |s| s.items.len()),
+            _ => 0,
+        };
+        if len > 0 {
+            self.selection.selected = (self.selection.selected as isize + delta).clamp(0, len as isize - 1) as usize;
+        }
+    }
+    
+    /// Open a URL in the browser and report the outcome
+    ///
+    /// A remote user's browser is out of reach, so the link is shown instead.
+    fn open_url(&mut self, url: &str) {
+        if self.is_remote() {
+            self.status = Some(format!("Link: {}", url));
+            return;
+        }
+        self.status = Some(match open::that_detached(url) {
+            Ok(()) => format!("Opened {}", url),
+            Err(err) => format!("Could not open {}: {}", url, err),
+        });
+    }
+    
+    /// Handle mouse events
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let target = self.hits.target_at(mouse.column, mouse.row).cloned();
+        if mouse.kind == MouseEventKind::Moved {
+            // Only redraw when the pointer moves onto a different target
+            if self.hits.hovered != target {
+                self.hits.hovered = target;
+                self.dirty = true;
+            }
+            return;
+        }
+        self.dirty = true;
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_wheel(-1, target),
+            MouseEventKind::ScrollDown => self.scroll_wheel(1, target),
+            MouseEventKind::Down(MouseButton::Left) => self.click(target),
+            _ => {}
+        }
+    }
+    
+    /// Scroll whatever is under the mouse wheel
+    fn scroll_wheel(&mut self, delta: isize, target: Option<HitTarget>) {
+        if self.popup.is_some() {
+            self.popup_scroll = if delta < 0 {
+                self.popup_scroll.saturating_sub(WHEEL_LINES)
+            } else {
+                self.popup_scroll.saturating_add(WHEEL_LINES)
+            };
+        } else if self.palette.active {
+            self.palette.select_relative(delta);
+        } else if self.search.active {
+            self.search.select_relative(delta);
+        } else if let Some(HitTarget::Tab(_)) = target {
+            if delta < 0 {
+                self.previous_section();
+            } else {
+                self.next_section();
+            }
+        } else {
+            self.select_item(delta);
+        }
+    }
+    
+    /// Handle a left click on a target
+    fn click(&mut self, target: Option<HitTarget>) {
+        if self.popup.is_some() {
+            // Clicking outside the popup closes it
+            if target != Some(HitTarget::Popup) {
+                self.popup = None;
+            }
+            return;
+        }
+        if self.palette.active || self.search.active {
+            return;
+        }
+        match target {
+            Some(HitTarget::Tab(index)) => self.navigate_to_section(index),
+            Some(HitTarget::Item(index)) => {
+                // A second click on the selected item shows its details
+                if index == self.selection.selected {
+                    self.perform(Action::OpenDetail);
+                } else {
+                    self.selection.selected = index;
+                }
+            }
+            Some(HitTarget::Link(url)) => self.open_url(&url),
+            Some(HitTarget::Popup) | None => {}
+        }
+    }
+    
     /// Navigate to the previous section
     fn previous_section(&mut self) {
         if !self.content_sections.is_empty() {
@@ -172,6 +1010,7 @@ impl App {
             } else {
                 self.current_section_index - 1
             };
+            self.reset_item_selection();
             self.update_view_from_section();
         }
     }
@@ -180,6 +1019,7 @@ impl App {
     fn next_section(&mut self) {
         if !self.content_sections.is_empty() {
             self.current_section_index = (self.current_section_index + 1) % self.content_sections.len();
+            self.reset_item_selection();
             self.update_view_from_section();
         }
     }
@@ -188,10 +1028,16 @@ impl App {
     fn navigate_to_section(&mut self, index: usize) {
         if index < self.content_sections.len() {
             self.current_section_index = index;
+            self.reset_item_selection();
             self.update_view_from_section();
         }
     }
     
+    /// Select the first item of a newly shown section
+    fn reset_item_selection(&mut self) {
+        self.selection = Selection::default();
+    }
+    
     /// Update the current view based on the selected section
     fn update_view_from_section(&mut self) {
         if self.content_sections.is_empty() {
@@ -200,6 +1046,11 @@ impl App {
         
         let section_name = &self.content_sections[self.current_section_index];
         
+        // Visiting a section marks it as read
+        if let Some(mark) = self.section_marks.get_mut(self.current_section_index) {
+            *mark = TabMark::Seen;
+        }
+        
         // Set the view based on the section name
         self.current_view = match section_name.as_str() {
             "Home" => View::Home,
@@ -218,49 +1069,319 @@ impl App {
     }
 
     /// Update state
+    ///
+    /// Sets the dirty flag when an animation needs to be redrawn.
     fn tick(&mut self) {
-        // TODO: Implement state updates
+        // Animate the loading spinner
+        if let Some(ref mut loading) = self.loading {
+            loading.spinner += 1;
+            self.dirty = true;
+        }
+    }
+    
+    /// Current loading spinner frame
+    fn spinner(&self, loading: &Loading) -> &'static str {
+        let frames = if self.icons.mode() == IconMode::Ascii { ASCII_SPINNER } else { SPINNER };
+        frames[loading.spinner % frames.len()]
     }
 
-    /// Render the UI
+    /// Render the UI to the terminal
     fn render(&mut self) -> Result<()> {
-        self.terminal.draw(|frame| {
-            let size = frame.size();
+        let Some(mut terminal) = self.terminal.take() else {
+            return Ok(());
+        };
+        let result = terminal.draw(|frame| self.draw(frame));
+        self.terminal = Some(terminal);
+        result
+    }
+
+    /// Show a loaded portfolio at a navigation index (0 is Home), without
+    /// loading anything
+    pub fn show(&mut self, portfolio: Portfolio, section: usize) {
+        self.load_data(portfolio);
+        self.navigate_to_section(section);
+    }
+
+    /// Whether the application draws on a remote terminal
+    fn is_remote(&self) -> bool {
+        self.terminal.as_ref().is_some_and(TerminalSession::is_remote)
+    }
+
+    /// Copy text to the terminal's clipboard
+    fn copy_to_clipboard(&mut self, text: &str) -> Result<()> {
+        match self.terminal {
+            Some(ref mut terminal) => terminal.copy(text),
+            None => Err(anyhow::anyhow!("No terminal to copy to")),
+        }
+    }
+
+    /// Active color theme
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Render the current view off-screen at the given size
+    pub fn screenshot(&mut self, width: u16, height: u16) -> Result<Buffer> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(|frame| self.draw(frame))?;
+        Ok(terminal.backend().buffer().clone())
+    }
+
+    /// Draw the whole screen into a frame
+    fn draw(&mut self, frame: &mut Frame) {
+        let hints = self.status_hints();
+        let spinner = self.loading.as_ref().map_or("", |loading| self.spinner(loading));
+        let message = match self.loading {
+            Some(ref loading) if self.formatted_portfolio.is_some() => {
+                Some(format!("{} Reloading {:.1}s", spinner, loading.started.elapsed().as_secs_f32()))
+            }
+            _ => self.status.clone(),
+        };
+        self.hits.clear();
+        let size = frame.size();
+        
+        // Create a block for the entire UI
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.theme.muted)
+            .style(self.theme.text.bg(self.theme.background))
+            .title(" Portfolio Viewer ")
+            .title_style(self.theme.heading);
+        
+        // Render the block
+        let inner_area = block.inner(size);
+        frame.render_widget(block, size);
+        
+        // Split off the tab bar and status line
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Tab bar
+                Constraint::Min(0),    // Current view
+                Constraint::Length(1), // Status line
+            ])
+            .split(inner_area);
+        let inner_area = chunks[1];
+        views::status::render(frame, chunks[2], hints, message.as_deref(), &self.theme);
+        
+        // Render the current view
+        if let Some(ref formatted_portfolio) = self.formatted_portfolio {
+            views::tabs::render(
+                frame,
+                chunks[0],
+                &self.content_sections,
+                &self.section_marks,
+                self.current_section_index,
+                &mut self.tab_offset,
+                &self.icons,
+                &mut self.hits,
+                &self.theme,
+            );
             
-            // Create a block for the entire UI
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(" Portfolio Viewer ")
-                .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+            match self.current_view {
+                View::Home => views::home::render(
+                    frame,
+                    inner_area,
+                    formatted_portfolio,
+                    &self.icons,
+                    &mut self.hits,
+                    &self.theme,
+                    &mut self.text_cache,
+                ),
+                View::Content(index) => {
+                    if index < formatted_portfolio.content_sections.len() {
+                        views::content::render(
+                            frame,
+                            inner_area,
+                            formatted_portfolio,
+                            index,
+                            &mut self.selection,
+                            &self.icons,
+                            &mut self.hits,
+                            &self.theme,
+                            &mut self.text_cache,
+                        );
+                    } else {
+                        // Fallback to home view if the index is out of bounds
+                        views::home::render(
+                            frame,
+                            inner_area,
+                            formatted_portfolio,
+                            &self.icons,
+                            &mut self.hits,
+                            &self.theme,
+                            &mut self.text_cache,
+                        );
+                    }
+                }
+            }
             
-            // Render the block
-            let inner_area = block.inner(size);
-            frame.render_widget(block, size);
+            // Render the search popup on top of the current view
+            if self.search.active {
+                views::search::render(frame, size, &self.search, &self.theme);
+            }
             
-            // Render the current view
-            if let Some(ref formatted_portfolio) = self.formatted_portfolio {
-                match self.current_view {
-                    View::Home => views::home::render(frame, inner_area, formatted_portfolio, &self.content_sections),
-                    View::Content(index) => {
-                        if index < formatted_portfolio.content_sections.len() {
-                            views::content::render(frame, inner_area, formatted_portfolio, index);
-                        } else {
-                            // Fallback to home view if the index is out of bounds
-                            views::home::render(frame, inner_area, formatted_portfolio, &self.content_sections);
-                        }
+            // Render the command palette on top of everything
+            if self.palette.active {
+                views::palette::render(frame, size, &self.palette, &self.theme);
+            }
+            
+            // Render the details, help or QR code popup
+            match self.popup {
+                Some(Popup::Help) => views::help::render(
+                    frame,
+                    size,
+                    &self.keymap,
+                    &mut self.popup_scroll,
+                    &mut self.hits,
+                    &self.theme,
+                ),
+                Some(Popup::Detail) => {
+                    let item = match self.current_view {
+                        View::Content(index) => formatted_portfolio
+                            .content_sections
+                            .get(index)
+                            .and_then(|section| section.items.get(self.selection.selected)),
+                        View::Home => None,
+                    };
+                    if let Some(item) = item {
+                        views::detail::render(
+                            frame,
+                            size,
+                            item,
+                            &self.icons,
+                            &mut self.popup_scroll,
+                            &mut self.hits,
+                            &self.theme,
+                            &mut self.text_cache,
+                        );
                     }
                 }
-            } else {
-                // Render loading message if portfolio data is not loaded yet
-                let loading = Paragraph::new("Loading portfolio data...")
-                    .style(Style::default().fg(Color::Yellow))
-                    .alignment(Alignment::Center)
-                    .block(Block::default().borders(Borders::NONE));
-                frame.render_widget(loading, inner_area);
+                Some(Popup::Qr) => {
+                    if let Some(target) = self.qr_targets.get(self.qr_index) {
+                        views::qr::render(
+                            frame,
+                            size,
+                            target,
+                            (self.qr_index, self.qr_targets.len()),
+                            &mut self.hits,
+                            &self.theme,
+                        );
+                    }
+                }
+                None => {}
             }
-        })?;
 
-        Ok(())
+            // Render the export prompt on top of everything
+            if let Some(ref input) = self.export_prompt {
+                views::prompt::render(frame, size, "Export to (.md, .html, .pdf, .json, .vcf, .gmi)", input, &self.theme);
+            }
+        } else if let Some(ref loading) = self.loading {
+            // Render progress while the first load is running
+            views::loading::render(
+                frame,
+                inner_area,
+                &loading.source,
+                loading.started.elapsed(),
+                spinner,
+                &self.theme,
+            );
+        } else if let Some(ref error) = self.load_error {
+            let cache_age = self
+                .cache_saved
+                .map(|saved| SystemTime::now().duration_since(saved).unwrap_or_default());
+            let remote = self.is_remote();
+            views::error::render(frame, inner_area, &self.config_path, error, cache_age, !remote, &self.theme);
+        }
+        
+        // Render the switch source prompt on top of the error screen
+        if let Some(ref input) = self.source_prompt {
+            views::prompt::render(frame, size, "Switch source", input, &self.theme);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::color::ColorDepth;
+    use crate::ui::terminal::RemoteOutput;
+
+    /// Headless application showing a small portfolio
+    fn loaded_app() -> App {
+        let args = <Args as clap::Parser>::parse_from(["portfolio-tui", "--config-path", "portfolio.yml"]);
+        let themes = ThemeSet::load(None, ColorDepth::TrueColor).unwrap();
+        let mut app = App::headless(args, Keymap::default(), themes).unwrap();
+        let portfolio = Parser::new().parse("name: Test\ndescription: About me\n").unwrap();
+        app.show(portfolio, 0);
+        app.dirty = false;
+        app
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn idle_ticks_do_not_redraw() {
+        let mut app = loaded_app();
+        for _ in 0..100 {
+            app.handle_event(Event::Tick).unwrap();
+        }
+        assert!(!app.dirty);
+    }
+
+    #[tokio::test]
+    async fn an_idle_app_draws_nothing_until_input() {
+        let path = std::env::temp_dir().join(format!("portfolio-tui-idle-{}.yml", std::process::id()));
+        std::fs::write(&path, "name: Test\ndescription: About me\n").unwrap();
+        let args = <Args as clap::Parser>::parse_from(["portfolio-tui", "--config-path", path.to_str().unwrap()]);
+        let themes = ThemeSet::load(None, ColorDepth::TrueColor).unwrap();
+        let (output, mut frames) = RemoteOutput::new();
+        let terminal = TerminalSession::remote(output, (80, 24), false).unwrap();
+        let mut app = App::remote(args, Keymap::default(), themes, terminal, Arc::default()).unwrap();
+        let (events, input) = EventHandler::remote();
+        let running = tokio::spawn(async move { app.run_with(events).await });
+
+        // Wait for the loaded portfolio to be drawn
+        let mut drawn = Vec::new();
+        while !String::from_utf8_lossy(&drawn).contains("About Me") {
+            let frame = time::timeout(Duration::from_secs(5), frames.recv()).await.unwrap().unwrap();
+            drawn.extend(frame);
+        }
+
+        // Several tick periods pass without a single frame
+        let idle = time::timeout(TICK_RATE * 6, frames.recv()).await;
+        assert!(idle.is_err(), "drew {:?} without input", idle);
+
+        input.send(Event::Resize(60, 20)).unwrap();
+        assert!(time::timeout(Duration::from_secs(1), frames.recv()).await.unwrap().is_some());
+
+        input.send(Event::Terminate).unwrap();
+        running.await.unwrap().unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn ticks_animate_the_spinner_while_loading() {
+        let mut app = loaded_app();
+        app.loading = Some(Loading {
+            source: app.config_path.clone(),
+            started: Instant::now(),
+            spinner: 0,
+        });
+        app.handle_event(Event::Tick).unwrap();
+        assert!(app.dirty);
+        assert_eq!(app.loading.as_ref().map(|loading| loading.spinner), Some(1));
+    }
+
+    #[test]
+    fn number_keys_count_tabs_from_home() {
+        let mut app = loaded_app();
+        let portfolio = Parser::new()
+            .parse("name: Test\ncontent:\n  - title: Projects\n    content: Some projects\n  - title: Talks\n    content: Some talks\n")
+            .unwrap();
+        app.show(portfolio, 0);
+
+        app.handle_key(KeyEvent::from(KeyCode::Char('3')));
+        assert_eq!(app.current_view, View::Content(1));
+        app.handle_key(KeyEvent::from(KeyCode::Char('1')));
+        assert_eq!(app.current_view, View::Home);
+    }
+}
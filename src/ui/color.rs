@@ -0,0 +1,174 @@
+//! Terminal color support
+//!
+//! Detects how many colors the terminal can show from the environment and
+//! maps theme colors down to what it supports.
+
+use ratatui::style::{Color, Style};
+use std::env;
+
+/// Colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// No colors, emphasis through text attributes only
+    Monochrome,
+    /// The 16 basic ANSI colors
+    Ansi16,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// 24-bit RGB colors
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detect the color depth from `NO_COLOR`, `CLICOLOR_FORCE`, `TERM` and `COLORTERM`
+    ///
+    /// `NO_COLOR` always wins. `CLICOLOR_FORCE` enables at least 16 colors
+    /// even on a dumb terminal.
+    pub fn detect() -> Self {
+        let set = |name: &str| env::var_os(name).is_some_and(|value| !value.is_empty());
+        let term = env::var("TERM").unwrap_or_default().to_lowercase();
+        let colorterm = env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        let forced = env::var("CLICOLOR_FORCE").is_ok_and(|value| !value.is_empty() && value != "0");
+
+        if set("NO_COLOR") {
+            ColorDepth::Monochrome
+        } else if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if term == "dumb" && forced {
+            ColorDepth::Ansi16
+        } else {
+            Self::from_term(&term)
+        }
+    }
+
+    /// Color depth of a terminal type such as `xterm-256color`
+    ///
+    /// Used for remote terminals, which only report their `TERM`.
+    pub fn from_term(term: &str) -> Self {
+        let term = term.to_lowercase();
+        if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else if term == "dumb" {
+            ColorDepth::Monochrome
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Map a color to the closest one this depth can show
+    ///
+    /// Returns `None` in monochrome mode.
+    pub fn map(&self, color: Color) -> Option<Color> {
+        match (self, color) {
+            (ColorDepth::Monochrome, _) => None,
+            (_, Color::Reset) => Some(Color::Reset),
+            (ColorDepth::TrueColor, color) => Some(color),
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Some(Color::Indexed(nearest_indexed(r, g, b))),
+            (ColorDepth::Ansi256, color) => Some(color),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => Some(nearest_basic(r, g, b)),
+            (ColorDepth::Ansi16, Color::Indexed(index)) => {
+                let (r, g, b) = indexed_rgb(index);
+                Some(nearest_basic(r, g, b))
+            }
+            (ColorDepth::Ansi16, color) => Some(color),
+        }
+    }
+
+    /// Map the foreground, background and underline colors of a style
+    pub fn map_style(&self, style: Style) -> Style {
+        Style {
+            fg: style.fg.and_then(|color| self.map(color)),
+            bg: style.bg.and_then(|color| self.map(color)),
+            underline_color: style.underline_color.and_then(|color| self.map(color)),
+            ..style
+        }
+    }
+}
+
+/// The 16 basic colors with their usual xterm RGB values
+const BASIC_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// RGB value of a color, or `None` for the terminal's default color
+pub fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index) => Some(indexed_rgb(index)),
+        color => BASIC_COLORS.iter().find(|(basic, _)| *basic == color).map(|(_, rgb)| *rgb),
+    }
+}
+
+/// Channel levels of the 6x6x6 color cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Squared distance between two RGB colors
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let dr = r1 as i32 - r2 as i32;
+    let dg = g1 as i32 - g2 as i32;
+    let db = b1 as i32 - b2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+/// Closest of the 16 basic colors
+fn nearest_basic(r: u8, g: u8, b: u8) -> Color {
+    BASIC_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// Closest entry of the color cube or grayscale ramp in the 256-color palette
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = 232 + ((average.saturating_sub(3)) / 10).min(23) as u8;
+
+    if distance(indexed_rgb(gray), (r, g, b)) < distance(indexed_rgb(cube), (r, g, b)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// RGB value of an entry in the 256-color palette
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASIC_COLORS[index as usize].1,
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
@@ -1,75 +1,110 @@
-//! Event handling module for future use
-//! 
-//! This module provides event handling capabilities for the application.
-//! It is currently not used but is kept for future enhancements.
-//! The warnings are suppressed with the #[allow(dead_code)] attribute.
+//! Event handling module
+//!
+//! Terminal input is read on a background thread and delivered to the
+//! application through a channel. The application waits on the channel and
+//! only redraws when an event changed something. Job control and termination
+//! signals are delivered on the same channel.
+//!
+//! Remote sessions have no terminal to read, so their input is decoded
+//! elsewhere and sent into the channel.
 
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent};
-use std::sync::mpsc;
+use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::thread;
-use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 
 /// Terminal events
-#[allow(dead_code)]
+#[derive(Debug, Clone)]
 pub enum Event {
     /// Key press
     Key(KeyEvent),
-    /// Tick
+    /// Mouse movement, click or wheel
+    Mouse(MouseEvent),
+    /// Terminal resized to a width and height
+    Resize(u16, u16),
+    /// Animation tick, generated by the application while it is loading
     Tick,
+    /// Stop requested from outside, by `SIGTSTP`
+    Suspend,
+    /// Terminated by `SIGTERM` or the terminal hung up with `SIGHUP`
+    Terminate,
 }
 
 /// Event handler
-#[allow(dead_code)]
 pub struct EventHandler {
-    /// Event sender
-    sender: mpsc::Sender<Event>,
     /// Event receiver
-    receiver: mpsc::Receiver<Event>,
-    /// Event handler thread
-    handler: thread::JoinHandle<()>,
+    receiver: mpsc::UnboundedReceiver<Event>,
 }
 
-#[allow(dead_code)]
 impl EventHandler {
-    /// Create a new event handler with the given tick rate
-    pub fn new(tick_rate: Duration) -> Self {
-        let (sender, receiver) = mpsc::channel();
-        let handler = {
-            let sender = sender.clone();
-            thread::spawn(move || {
-                let mut last_tick = Instant::now();
-                loop {
-                    let timeout = tick_rate
-                        .checked_sub(last_tick.elapsed())
-                        .unwrap_or_else(|| Duration::from_secs(0));
+    /// Create a new event handler reading the terminal
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let input_sender = sender.clone();
+        thread::spawn(move || {
+            let sender = input_sender;
+            // Block until input arrives; stop when the terminal can no longer be read
+            loop {
+                let event = match event::read() {
+                    Ok(CrosstermEvent::Key(key)) => Some(Event::Key(key)),
+                    Ok(CrosstermEvent::Mouse(mouse)) => Some(Event::Mouse(mouse)),
+                    Ok(CrosstermEvent::Resize(width, height)) => Some(Event::Resize(width, height)),
+                    Ok(_) => None,
+                    Err(_) => break,
+                };
+                if let Some(event) = event
+                    && sender.send(event).is_err()
+                {
+                    break;
+                }
+            }
+        });
 
-                    if event::poll(timeout).unwrap() {
-                        if let CrosstermEvent::Key(key) = event::read().unwrap() {
-                            if sender.send(Event::Key(key)).is_err() {
-                                break;
-                            }
-                        }
-                    }
+        #[cfg(unix)]
+        forward_signals(sender);
 
-                    if last_tick.elapsed() >= tick_rate {
-                        if sender.send(Event::Tick).is_err() {
-                            break;
-                        }
-                        last_tick = Instant::now();
-                    }
-                }
-            })
-        };
+        Self { receiver }
+    }
 
-        Self {
-            sender,
-            receiver,
-            handler,
-        }
+    /// Create an event handler for a remote terminal
+    ///
+    /// Input and resizes are sent through the returned sender.
+    pub fn remote() -> (Self, mpsc::UnboundedSender<Event>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (Self { receiver }, sender)
     }
 
-    /// Receive the next event
-    pub fn next(&self) -> Result<Event, mpsc::RecvError> {
-        self.receiver.recv()
+    /// Wait for the next event
+    ///
+    /// Returns `None` once the input thread has stopped.
+    pub async fn next(&mut self) -> Option<Event> {
+        self.receiver.recv().await
     }
-}
\ No newline at end of file
+}
+
+/// Turn job control and termination signals into events
+///
+/// Must be called from within the tokio runtime. Signals that cannot be
+/// registered keep their default behaviour.
+#[cfg(unix)]
+fn forward_signals(sender: mpsc::UnboundedSender<Event>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let signals = [
+        (SignalKind::from_raw(libc::SIGTSTP), Event::Suspend),
+        (SignalKind::terminate(), Event::Terminate),
+        (SignalKind::hangup(), Event::Terminate),
+    ];
+    for (kind, event) in signals {
+        let Ok(mut stream) = signal(kind) else {
+            continue;
+        };
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            while stream.recv().await.is_some() {
+                if sender.send(event.clone()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
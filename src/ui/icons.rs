@@ -0,0 +1,234 @@
+//! Icon resolution for Font Awesome and Devicon class names
+//!
+//! The Jekyll theme describes icons with web font classes such as
+//! `fab fa-github` or `devicon-rust-plain`. In the terminal these are mapped
+//! to Nerd Font glyphs, plain Unicode symbols or short ASCII labels.
+
+/// How icons should be drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconMode {
+    /// Nerd Font private use codepoints
+    NerdFont,
+    /// Plain Unicode symbols and emoji
+    Unicode,
+    /// ASCII-only labels
+    Ascii,
+}
+
+impl IconMode {
+    /// Icon mode selected by the `--ascii` and `--nerd-fonts` flags
+    pub fn from_flags(ascii: bool, nerd_fonts: bool) -> Self {
+        if ascii {
+            IconMode::Ascii
+        } else if nerd_fonts {
+            IconMode::NerdFont
+        } else {
+            IconMode::Unicode
+        }
+    }
+}
+
+/// Glyphs for a single icon name: (name, nerd font, unicode, ascii)
+type IconEntry = (&'static str, &'static str, &'static str, &'static str);
+
+/// Known icon names
+const ICONS: &[IconEntry] = &[
+    ("envelope", "\u{f0e0}", "✉", "[@]"),
+    ("globe", "\u{f0ac}", "🌐", "[www]"),
+    ("link", "\u{f0c1}", "🔗", "[url]"),
+    ("external-link", "\u{f08e}", "↗", "[->]"),
+    ("github", "\u{f09b}", "🐙", "[gh]"),
+    ("gitlab", "\u{f296}", "🦊", "[gl]"),
+    ("twitter", "\u{f099}", "🐦", "[tw]"),
+    ("linkedin", "\u{f0e1}", "💼", "[in]"),
+    ("discord", "\u{f066f}", "💬", "[dc]"),
+    ("stack-overflow", "\u{f16c}", "📚", "[so]"),
+    ("mastodon", "\u{f0ad1}", "🐘", "[md]"),
+    ("youtube", "\u{f16a}", "📺", "[yt]"),
+    ("instagram", "\u{f16d}", "📷", "[ig]"),
+    ("facebook", "\u{f09a}", "📘", "[fb]"),
+    ("medium", "\u{f23a}", "📝", "[med]"),
+    ("rss", "\u{f09e}", "📡", "[rss]"),
+    ("phone", "\u{f095}", "☎", "[tel]"),
+    ("map-marker", "\u{f041}", "📍", "[loc]"),
+    ("file", "\u{f15b}", "📄", "[doc]"),
+    ("file-pdf", "\u{f1c1}", "📄", "[pdf]"),
+    ("home", "\u{f015}", "🏠", "[~]"),
+    ("user", "\u{f007}", "👤", "[me]"),
+    ("briefcase", "\u{f0b1}", "💼", "[job]"),
+    ("graduation-cap", "\u{f19d}", "🎓", "[edu]"),
+    ("code", "\u{f121}", "💻", "[</>]"),
+    ("star", "\u{f005}", "★", "[*]"),
+    ("trophy", "\u{f091}", "🏆", "[#1]"),
+    ("book", "\u{f02d}", "📖", "[book]"),
+    ("quote-left", "\u{f10d}", "❝", "[\"]"),
+    ("heart", "\u{f004}", "♥", "[<3]"),
+    ("comment", "\u{f075}", "💬", "[...]"),
+    ("rust", "\u{e7a8}", "🦀", "[rs]"),
+    ("python", "\u{e73c}", "🐍", "[py]"),
+    ("javascript", "\u{e74e}", "📜", "[js]"),
+    ("docker", "\u{e7b0}", "🐳", "[dkr]"),
+    ("linux", "\u{f17c}", "🐧", "[lnx]"),
+];
+
+/// Alternative spellings of icon names
+const ALIASES: &[(&str, &str)] = &[
+    ("email", "envelope"),
+    ("mail", "envelope"),
+    ("envelope-o", "envelope"),
+    ("website", "globe"),
+    ("web", "globe"),
+    ("globe-americas", "globe"),
+    ("earth", "globe"),
+    ("github-alt", "github"),
+    ("github-square", "github"),
+    ("twitter-square", "twitter"),
+    ("x-twitter", "twitter"),
+    ("linkedin-in", "linkedin"),
+    ("stackoverflow", "stack-overflow"),
+    ("square-rss", "rss"),
+    ("rss-square", "rss"),
+    ("location-dot", "map-marker"),
+    ("map-marker-alt", "map-marker"),
+    ("house", "home"),
+    ("external-link-alt", "external-link"),
+    ("arrow-up-right-from-square", "external-link"),
+    ("file-alt", "file"),
+    ("laptop-code", "code"),
+    ("terminal", "code"),
+    ("user-graduate", "graduation-cap"),
+    ("school", "graduation-cap"),
+    ("award", "trophy"),
+    ("medal", "trophy"),
+    ("quote-right", "quote-left"),
+    ("js", "javascript"),
+    ("python-plain", "python"),
+];
+
+/// Style classes that carry no icon name
+const STYLE_CLASSES: &[&str] = &[
+    "fa", "fab", "fas", "far", "fal", "fad", "fat", "fa-solid", "fa-brands", "fa-regular",
+    "fa-light", "fa-duotone", "fa-thin", "fa-fw", "fa-lg", "fa-xs", "fa-sm", "fa-xl",
+    "fa-2x", "fa-3x", "fa-4x", "fa-5x", "colored",
+];
+
+/// Resolves icon class names to glyphs for the active mode
+#[derive(Debug, Clone, Copy)]
+pub struct Icons {
+    mode: IconMode,
+}
+
+impl Icons {
+    /// Create a new icon resolver
+    pub fn new(mode: IconMode) -> Self {
+        Self { mode }
+    }
+
+    /// Active icon mode
+    pub fn mode(&self) -> IconMode {
+        self.mode
+    }
+
+    /// Resolve a Font Awesome or Devicon class string to a glyph
+    pub fn resolve(&self, class: &str) -> Option<&'static str> {
+        let name = normalize(class)?;
+        let name = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == name)
+            .map_or(name.as_str(), |(_, target)| target);
+
+        ICONS
+            .iter()
+            .find(|(icon, ..)| *icon == name)
+            .map(|&(_, nerd, unicode, ascii)| match self.mode {
+                IconMode::NerdFont => nerd,
+                IconMode::Unicode => unicode,
+                IconMode::Ascii => ascii,
+            })
+    }
+
+    /// Prefix a label with the glyph for `class`, if one is known
+    pub fn label(&self, class: &str, text: &str) -> String {
+        match self.resolve(class) {
+            Some(glyph) => format!("{} {}", glyph, text),
+            None => text.to_string(),
+        }
+    }
+
+    /// Guess an icon for a section from its title
+    pub fn section(&self, title: &str) -> Option<&'static str> {
+        let title = title.to_lowercase();
+        let name = if title == "home" {
+            "home"
+        } else if title.contains("project") || title.contains("portfolio") {
+            "code"
+        } else if title.contains("experience") || title.contains("work") || title.contains("career") {
+            "briefcase"
+        } else if title.contains("education") || title.contains("school") || title.contains("degree") {
+            "graduation-cap"
+        } else if title.contains("about") {
+            "user"
+        } else if title.contains("skill") {
+            "star"
+        } else if title.contains("testimonial") || title.contains("quote") || title.contains("recommend") {
+            "quote-left"
+        } else if title.contains("award") || title.contains("achievement") || title.contains("certif") {
+            "trophy"
+        } else if title.contains("publication") || title.contains("book") {
+            "book"
+        } else if title.contains("blog") || title.contains("post") {
+            "rss"
+        } else if title.contains("contact") {
+            "envelope"
+        } else {
+            return None;
+        };
+        self.resolve(name)
+    }
+
+    /// Prefix a section title with its guessed icon
+    pub fn section_label(&self, title: &str) -> String {
+        match self.section(title) {
+            Some(glyph) => format!("{} {}", glyph, title),
+            None => title.to_string(),
+        }
+    }
+}
+
+/// Devicon class suffixes naming a variant of the same icon
+const DEVICON_VARIANTS: &[&str] = &["-plain", "-original", "-line", "-wordmark"];
+
+/// Extract the bare icon name from a class string
+fn normalize(class: &str) -> Option<String> {
+    class
+        .split_whitespace()
+        .map(|token| token.to_lowercase())
+        .filter(|token| !STYLE_CLASSES.contains(&token.as_str()))
+        .map(|token| {
+            if let Some(mut name) = token.strip_prefix("devicon-") {
+                // Variants combine, as in `rust-plain-wordmark`
+                while let Some(base) = DEVICON_VARIANTS.iter().find_map(|variant| name.strip_suffix(variant)) {
+                    name = base;
+                }
+                name.to_string()
+            } else if let Some(name) = token.strip_prefix("fa-") {
+                name.to_string()
+            } else {
+                token
+            }
+        })
+        .find(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_names_lose_their_style_and_variant() {
+        assert_eq!(normalize("fab fa-github").as_deref(), Some("github"));
+        assert_eq!(normalize("devicon-rust-plain").as_deref(), Some("rust"));
+        assert_eq!(normalize("devicon-rust-plain-wordmark").as_deref(), Some("rust"));
+        assert_eq!(normalize("devicon-python-original-wordmark colored").as_deref(), Some("python"));
+    }
+}
@@ -0,0 +1,54 @@
+//! Inline rendering
+//!
+//! Prints the home card into the normal screen below the prompt, like
+//! `neofetch`, instead of opening the fullscreen viewer. The output stays in
+//! the terminal history.
+
+use anyhow::{Context, Result};
+use ratatui::backend::CrosstermBackend;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use std::io;
+
+use crate::config::Args;
+use crate::data;
+use crate::processor::formatter::TextCache;
+use crate::processor::Formatter;
+use crate::ui::icons::{IconMode, Icons};
+use crate::ui::mouse::HitMap;
+use crate::ui::theme::ThemeSet;
+use crate::ui::views;
+
+/// Load the portfolio and print the home view `args.inline_height` lines high
+pub async fn print(args: &Args, themes: &ThemeSet) -> Result<()> {
+    let portfolio = data::load(&args.config_path).await?;
+    let theme = themes.select(args.theme.as_deref(), portfolio.darkmode.unwrap_or(true))?;
+    let formatted = Formatter::new().format(&portfolio);
+    let icons = Icons::new(IconMode::from_flags(args.ascii, args.nerd_fonts));
+
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(io::stdout()),
+        TerminalOptions {
+            viewport: Viewport::Inline(args.inline_height),
+        },
+    )
+    .context("--inline needs to run in a terminal")?;
+    let mut area = Default::default();
+    terminal.draw(|frame| {
+        area = frame.size();
+        views::home::render(
+            frame,
+            area,
+            &formatted,
+            &icons,
+            &mut HitMap::default(),
+            theme,
+            &mut TextCache::default(),
+        );
+    })?;
+
+    // Leave the cursor on the line below the card
+    terminal.set_cursor(0, area.bottom().saturating_sub(1))?;
+    terminal.show_cursor()?;
+    println!();
+    Ok(())
+}
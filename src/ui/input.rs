@@ -0,0 +1,337 @@
+//! Terminal input decoding
+//!
+//! Turns the bytes a remote terminal sends, over SSH or a WebSocket, into
+//! key and mouse events. Locally crossterm does this; remote input arrives as
+//! raw bytes with the escape sequences of an xterm-compatible terminal.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::ui::events::Event;
+
+/// Escape character starting control sequences
+const ESC: u8 = 0x1b;
+
+/// Longest incomplete input kept for the next chunk, in bytes
+const MAX_PENDING: usize = 64;
+
+/// Decodes the input of one connection into events
+///
+/// Terminals send each key press as a whole, but a read can still end in
+/// the middle of a UTF-8 character or a control sequence; that tail is kept
+/// and decoded with the next chunk. A lone escape is the Esc key, and an
+/// escape followed by a character is that character with Alt. Unknown
+/// sequences are dropped.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    /// Start of a character or sequence waiting for the rest of its bytes
+    pending: Vec<u8>,
+}
+
+impl Decoder {
+    /// Decode the next chunk of input
+    pub fn decode(&mut self, chunk: &[u8]) -> Vec<Event> {
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        let mut rest = &bytes[..];
+        while let Some(&first) = rest.first() {
+            let (event, length) = match first {
+                ESC => escape(rest),
+                _ => {
+                    let (key, length) = plain(rest);
+                    (key.map(Event::Key), length)
+                }
+            };
+            // Incomplete at the end of the chunk; anything longer is garbage
+            if length == 0 {
+                if rest.len() <= MAX_PENDING {
+                    self.pending = rest.to_vec();
+                }
+                break;
+            }
+            events.extend(event);
+            rest = &rest[length..];
+        }
+        events
+    }
+}
+
+/// Key for a byte or UTF-8 character outside of escape sequences
+///
+/// A length of 0 means the character is cut off.
+fn plain(bytes: &[u8]) -> (Option<KeyEvent>, usize) {
+    let key = |code, modifiers| Some(KeyEvent::new(code, modifiers));
+    match bytes[0] {
+        b'\r' | b'\n' => (key(KeyCode::Enter, KeyModifiers::NONE), 1),
+        b'\t' => (key(KeyCode::Tab, KeyModifiers::NONE), 1),
+        0x7f | 0x08 => (key(KeyCode::Backspace, KeyModifiers::NONE), 1),
+        0x00 => (key(KeyCode::Char(' '), KeyModifiers::CONTROL), 1),
+        byte @ 0x01..=0x1a => (key(KeyCode::Char((b'a' + byte - 1) as char), KeyModifiers::CONTROL), 1),
+        byte @ 0x1c..=0x1f => (key(KeyCode::Char((b'4' + byte - 0x1c) as char), KeyModifiers::CONTROL), 1),
+        _ => {
+            let length = utf8_length(bytes[0]);
+            let continued = bytes.iter().skip(1).take(length - 1).all(|byte| (0x80..=0xbf).contains(byte));
+            if bytes.len() < length && continued {
+                return (None, 0);
+            }
+            let Some(c) = std::str::from_utf8(&bytes[..length.min(bytes.len())]).ok().and_then(|text| text.chars().next()) else {
+                return (None, 1);
+            };
+            let modifiers = if c.is_uppercase() { KeyModifiers::SHIFT } else { KeyModifiers::NONE };
+            (key(KeyCode::Char(c), modifiers), length)
+        }
+    }
+}
+
+/// Length of a UTF-8 character from its first byte
+fn utf8_length(byte: u8) -> usize {
+    match byte {
+        0xf0.. => 4,
+        0xe0.. => 3,
+        0xc0.. => 2,
+        _ => 1,
+    }
+}
+
+/// Event for a sequence starting with escape, and the bytes it used
+///
+/// A length of 0 means the sequence is cut off.
+fn escape(bytes: &[u8]) -> (Option<Event>, usize) {
+    match (bytes.get(1), bytes.get(2)) {
+        (None | Some(&ESC), _) => (Some(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))), 1),
+        (Some(b'['), _) => csi(bytes),
+        (Some(b'O'), Some(&last)) => {
+            let code = match last {
+                b'P'..=b'S' => Some(KeyCode::F(last - b'P' + 1)),
+                _ => cursor_key(last),
+            };
+            (code.map(|code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE))), 3)
+        }
+        // Escape and a character, including a lone `O`, are Alt and the character
+        (Some(_), _) => match plain(&bytes[1..]) {
+            (_, 0) => (None, 0),
+            (key, length) => {
+                let key = key.map(|mut key| {
+                    key.modifiers |= KeyModifiers::ALT;
+                    Event::Key(key)
+                });
+                (key, length + 1)
+            }
+        },
+    }
+}
+
+/// Arrow, Home and End keys by the last byte of their sequence
+fn cursor_key(last: u8) -> Option<KeyCode> {
+    match last {
+        b'A' => Some(KeyCode::Up),
+        b'B' => Some(KeyCode::Down),
+        b'C' => Some(KeyCode::Right),
+        b'D' => Some(KeyCode::Left),
+        b'H' => Some(KeyCode::Home),
+        b'F' => Some(KeyCode::End),
+        _ => None,
+    }
+}
+
+/// Event for a control sequence `ESC [ parameters final`
+///
+/// A length of 0 means the sequence is cut off. A sequence broken by a byte
+/// that cannot be part of it is dropped up to that byte.
+fn csi(bytes: &[u8]) -> (Option<Event>, usize) {
+    let Some(end) = bytes.iter().skip(2).position(|byte| !(0x20..=0x3f).contains(byte)).map(|index| index + 2) else {
+        return (None, 0);
+    };
+    if !(0x40..=0x7e).contains(&bytes[end]) {
+        return (None, end);
+    }
+    let parameters = std::str::from_utf8(&bytes[2..end]).unwrap_or_default();
+    let last = bytes[end];
+    let length = end + 1;
+
+    if let Some(parameters) = parameters.strip_prefix('<') {
+        return (mouse(parameters, last).map(Event::Mouse), length);
+    }
+
+    let numbers: Vec<u16> = parameters.split(';').map(|number| number.parse().unwrap_or(1)).collect();
+    let modifiers = numbers.get(1).map_or(KeyModifiers::NONE, |&number| modifiers(number.saturating_sub(1)));
+    let code = match last {
+        b'Z' => return (Some(Event::Key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT))), length),
+        b'~' => match numbers[0] {
+            1 | 7 => Some(KeyCode::Home),
+            2 => Some(KeyCode::Insert),
+            3 => Some(KeyCode::Delete),
+            4 | 8 => Some(KeyCode::End),
+            5 => Some(KeyCode::PageUp),
+            6 => Some(KeyCode::PageDown),
+            number @ 11..=15 => Some(KeyCode::F((number - 10) as u8)),
+            number @ 17..=21 => Some(KeyCode::F((number - 11) as u8)),
+            number @ 23..=24 => Some(KeyCode::F((number - 12) as u8)),
+            _ => None,
+        },
+        b'P'..=b'S' => Some(KeyCode::F(last - b'P' + 1)),
+        _ => cursor_key(last),
+    };
+    (code.map(|code| Event::Key(KeyEvent::new(code, modifiers))), length)
+}
+
+/// Modifiers from the bits xterm uses in key and mouse sequences
+fn modifiers(bits: u16) -> KeyModifiers {
+    let mut modifiers = KeyModifiers::NONE;
+    if bits & 1 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if bits & 2 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if bits & 4 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    modifiers
+}
+
+/// Mouse event from an SGR mouse report `ESC [ < button ; column ; row M`
+fn mouse(parameters: &str, last: u8) -> Option<MouseEvent> {
+    let mut numbers = parameters.split(';').map(|number| number.parse::<u16>().ok());
+    let (Some(Some(button)), Some(Some(column)), Some(Some(row))) = (numbers.next(), numbers.next(), numbers.next())
+    else {
+        return None;
+    };
+
+    let pressed = match button & 0b11 {
+        0 => Some(MouseButton::Left),
+        1 => Some(MouseButton::Middle),
+        2 => Some(MouseButton::Right),
+        _ => None,
+    };
+    let kind = match (button & 64 != 0, button & 32 != 0, last) {
+        (true, _, _) if button & 1 == 0 => MouseEventKind::ScrollUp,
+        (true, _, _) => MouseEventKind::ScrollDown,
+        (false, true, _) => pressed.map_or(MouseEventKind::Moved, MouseEventKind::Drag),
+        (false, false, b'm') => MouseEventKind::Up(pressed.unwrap_or(MouseButton::Left)),
+        (false, false, _) => MouseEventKind::Down(pressed?),
+    };
+    Some(MouseEvent {
+        kind,
+        column: column.saturating_sub(1),
+        row: row.saturating_sub(1),
+        modifiers: modifiers(button >> 2),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keys decoded from chunks fed one after another to one decoder
+    fn keys(chunks: &[&[u8]]) -> Vec<KeyEvent> {
+        let mut decoder = Decoder::default();
+        chunks.iter().flat_map(|chunk| key_events(decoder.decode(chunk))).collect()
+    }
+
+    fn key_events(events: Vec<Event>) -> Vec<KeyEvent> {
+        events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Key(key) => Some(key),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn plain_keys() {
+        assert_eq!(
+            keys(&[b"aB\r\x7f\x03"]),
+            vec![
+                key(KeyCode::Char('a'), KeyModifiers::NONE),
+                key(KeyCode::Char('B'), KeyModifiers::SHIFT),
+                key(KeyCode::Enter, KeyModifiers::NONE),
+                key(KeyCode::Backspace, KeyModifiers::NONE),
+                key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ]
+        );
+    }
+
+    #[test]
+    fn lone_escape_is_esc() {
+        assert_eq!(keys(&[b"\x1b"]), vec![key(KeyCode::Esc, KeyModifiers::NONE)]);
+        assert_eq!(keys(&[b"\x1b\x1b"]), vec![key(KeyCode::Esc, KeyModifiers::NONE); 2]);
+        assert_eq!(keys(&[b"\x1ba"]), vec![key(KeyCode::Char('a'), KeyModifiers::ALT)]);
+    }
+
+    #[test]
+    fn truncated_ss3_is_alt_o() {
+        assert_eq!(keys(&[b"\x1bO"]), vec![key(KeyCode::Char('O'), KeyModifiers::SHIFT | KeyModifiers::ALT)]);
+        assert_eq!(keys(&[b"\x1bOA"]), vec![key(KeyCode::Up, KeyModifiers::NONE)]);
+        assert_eq!(keys(&[b"\x1bOQ"]), vec![key(KeyCode::F(2), KeyModifiers::NONE)]);
+    }
+
+    #[test]
+    fn truncated_csi_waits_for_the_rest() {
+        let mut decoder = Decoder::default();
+        assert!(decoder.decode(b"\x1b[").is_empty());
+        assert!(decoder.decode(b"1;5").is_empty());
+        assert_eq!(
+            key_events(decoder.decode(b"Ax")),
+            vec![key(KeyCode::Up, KeyModifiers::CONTROL), key(KeyCode::Char('x'), KeyModifiers::NONE)]
+        );
+    }
+
+    #[test]
+    fn broken_csi_is_dropped() {
+        assert_eq!(keys(&[b"\x1b[1\x1b[B"]), vec![key(KeyCode::Down, KeyModifiers::NONE)]);
+        assert_eq!(keys(&[b"\x1b[1\x03"]), vec![key(KeyCode::Char('c'), KeyModifiers::CONTROL)]);
+    }
+
+    #[test]
+    fn overlong_sequence_is_dropped() {
+        let mut garbage = b"\x1b[".to_vec();
+        garbage.extend([b'1'; MAX_PENDING]);
+        assert_eq!(keys(&[&garbage, b"a"]), vec![key(KeyCode::Char('a'), KeyModifiers::NONE)]);
+    }
+
+    #[test]
+    fn utf8_split_across_chunks() {
+        let bytes = "é🦆".as_bytes();
+        assert_eq!(
+            keys(&[&bytes[..1], &bytes[1..3], &bytes[3..5], &bytes[5..]]),
+            vec![key(KeyCode::Char('é'), KeyModifiers::NONE), key(KeyCode::Char('🦆'), KeyModifiers::NONE)]
+        );
+        assert_eq!(keys(&[b"\x1b\xc3", b"\xa9"]), vec![key(KeyCode::Char('é'), KeyModifiers::ALT)]);
+    }
+
+    #[test]
+    fn invalid_utf8_is_skipped() {
+        assert_eq!(keys(&[b"\xa9\xc3a"]), vec![key(KeyCode::Char('a'), KeyModifiers::NONE)]);
+    }
+
+    #[test]
+    fn mouse_report() {
+        let mut decoder = Decoder::default();
+        let events = decoder.decode(b"\x1b[<0;10;5M");
+        let [Event::Mouse(mouse)] = events.as_slice() else {
+            panic!("expected one mouse event, got {:?}", events);
+        };
+        assert_eq!(mouse.kind, MouseEventKind::Down(MouseButton::Left));
+        assert_eq!((mouse.column, mouse.row), (9, 4));
+    }
+
+    #[test]
+    fn every_prefix_decodes_without_panicking() {
+        let input = "a\x1b[1;5A\x1bOP\x1b[<64;3;4M\x1b[15~é\x1bx\x1b".as_bytes();
+        for split in 0..=input.len() {
+            let mut decoder = Decoder::default();
+            decoder.decode(&input[..split]);
+            decoder.decode(&input[split..]);
+        }
+        for end in 0..=input.len() {
+            Decoder::default().decode(&input[..end]);
+        }
+    }
+}
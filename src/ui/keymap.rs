@@ -0,0 +1,402 @@
+//! Key bindings
+//!
+//! A [`Keymap`] starts from one of the built-in presets and applies the
+//! per-action overrides from the settings file.
+
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::settings::KeymapSettings;
+use crate::ui::actions::{Action, ErrorCommand, PopupCommand, PromptCommand};
+
+/// A key chord bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    /// Key code
+    pub code: KeyCode,
+    /// Required modifiers
+    pub modifiers: KeyModifiers,
+    /// Bound action
+    pub action: Action,
+}
+
+impl Binding {
+    /// Bind a key without modifiers
+    const fn key(code: KeyCode, action: Action) -> Self {
+        Self { code, modifiers: KeyModifiers::NONE, action }
+    }
+
+    /// Bind a character key without modifiers
+    const fn char(c: char, action: Action) -> Self {
+        Self::key(KeyCode::Char(c), action)
+    }
+
+    /// Bind a character key with the Control modifier
+    const fn ctrl(c: char, action: Action) -> Self {
+        Self { code: KeyCode::Char(c), modifiers: KeyModifiers::CONTROL, action }
+    }
+
+    /// Bind a character key with the Alt modifier
+    const fn alt(c: char, action: Action) -> Self {
+        Self { code: KeyCode::Char(c), modifiers: KeyModifiers::ALT, action }
+    }
+
+    /// Whether a key event triggers this binding
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code && self.modifiers == normalize_modifiers(key.code, key.modifiers)
+    }
+
+    /// Display label for the key chord
+    pub fn label(&self) -> String {
+        key_label(self.code, self.modifiers)
+    }
+}
+
+/// Keys shared by all presets
+const COMMON_BINDINGS: &[Binding] = &[
+    Binding::key(KeyCode::Enter, Action::OpenDetail),
+    Binding::char('?', Action::Help),
+    Binding::key(KeyCode::F(1), Action::Help),
+    Binding::ctrl('z', Action::Suspend),
+    Binding::char('1', Action::GoToSection(0)),
+    Binding::char('2', Action::GoToSection(1)),
+    Binding::char('3', Action::GoToSection(2)),
+    Binding::char('4', Action::GoToSection(3)),
+    Binding::char('5', Action::GoToSection(4)),
+    Binding::char('6', Action::GoToSection(5)),
+    Binding::char('7', Action::GoToSection(6)),
+    Binding::char('8', Action::GoToSection(7)),
+    Binding::char('9', Action::GoToSection(8)),
+    Binding::char('0', Action::GoToSection(9)),
+];
+
+/// Default key bindings
+const DEFAULT_PRESET: &[Binding] = &[
+    Binding::char('q', Action::Quit),
+    Binding::ctrl('c', Action::Quit),
+    Binding::char('h', Action::Home),
+    Binding::key(KeyCode::Left, Action::PreviousSection),
+    Binding::char('j', Action::PreviousSection),
+    Binding::key(KeyCode::Right, Action::NextSection),
+    Binding::char('l', Action::NextSection),
+    Binding::key(KeyCode::Up, Action::SelectPrevious),
+    Binding::key(KeyCode::Down, Action::SelectNext),
+    Binding::char('/', Action::Search),
+    Binding::char('n', Action::NextMatch),
+    Binding::char('N', Action::PreviousMatch),
+    Binding::char('o', Action::OpenLink),
+    Binding::char('y', Action::CopyLink),
+    Binding::char('Q', Action::QrCode),
+    Binding::char('e', Action::Export),
+    Binding::char('S', Action::Screenshot),
+    Binding::char('r', Action::Reload),
+    Binding::char('t', Action::ToggleTheme),
+    Binding::char(':', Action::CommandPalette),
+    Binding::ctrl('p', Action::CommandPalette),
+];
+
+/// Vim style key bindings
+const VIM_PRESET: &[Binding] = &[
+    Binding::char('q', Action::Quit),
+    Binding::ctrl('c', Action::Quit),
+    Binding::char('g', Action::Home),
+    Binding::key(KeyCode::Left, Action::PreviousSection),
+    Binding::char('h', Action::PreviousSection),
+    Binding::key(KeyCode::Right, Action::NextSection),
+    Binding::char('l', Action::NextSection),
+    Binding::key(KeyCode::Up, Action::SelectPrevious),
+    Binding::char('k', Action::SelectPrevious),
+    Binding::key(KeyCode::Down, Action::SelectNext),
+    Binding::char('j', Action::SelectNext),
+    Binding::char('/', Action::Search),
+    Binding::char('n', Action::NextMatch),
+    Binding::char('N', Action::PreviousMatch),
+    Binding::char('o', Action::OpenLink),
+    Binding::char('y', Action::CopyLink),
+    Binding::char('Q', Action::QrCode),
+    Binding::char('e', Action::Export),
+    Binding::char('S', Action::Screenshot),
+    Binding::char('r', Action::Reload),
+    Binding::char('t', Action::ToggleTheme),
+    Binding::char(':', Action::CommandPalette),
+    Binding::ctrl('p', Action::CommandPalette),
+];
+
+/// Emacs style key bindings
+const EMACS_PRESET: &[Binding] = &[
+    Binding::ctrl('c', Action::Quit),
+    Binding::ctrl('q', Action::Quit),
+    Binding::alt('<', Action::Home),
+    Binding::key(KeyCode::Left, Action::PreviousSection),
+    Binding::ctrl('b', Action::PreviousSection),
+    Binding::key(KeyCode::Right, Action::NextSection),
+    Binding::ctrl('f', Action::NextSection),
+    Binding::key(KeyCode::Up, Action::SelectPrevious),
+    Binding::ctrl('p', Action::SelectPrevious),
+    Binding::key(KeyCode::Down, Action::SelectNext),
+    Binding::ctrl('n', Action::SelectNext),
+    Binding::ctrl('s', Action::Search),
+    Binding::alt('n', Action::NextMatch),
+    Binding::alt('p', Action::PreviousMatch),
+    Binding::alt('o', Action::OpenLink),
+    Binding::alt('w', Action::CopyLink),
+    Binding::alt('q', Action::QrCode),
+    Binding::alt('e', Action::Export),
+    Binding::alt('s', Action::Screenshot),
+    Binding::alt('r', Action::Reload),
+    Binding::alt('t', Action::ToggleTheme),
+    Binding::alt('x', Action::CommandPalette),
+];
+
+/// Names of the built-in presets
+pub const PRESETS: &[&str] = &["default", "vim", "emacs"];
+
+/// Active key bindings
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset("default").expect("default preset exists")
+    }
+}
+
+impl Keymap {
+    /// Create a keymap from a built-in preset
+    pub fn preset(name: &str) -> Result<Self> {
+        let preset = match name {
+            "default" => DEFAULT_PRESET,
+            "vim" => VIM_PRESET,
+            "emacs" => EMACS_PRESET,
+            other => bail!("Unknown keymap preset '{}', expected one of: {}", other, PRESETS.join(", ")),
+        };
+        let bindings = preset.iter().chain(COMMON_BINDINGS).copied().collect();
+        Ok(Self { bindings })
+    }
+
+    /// Build a keymap from settings, optionally overriding the preset
+    ///
+    /// Fails if a key chord cannot be parsed, an action is unknown, or the
+    /// same chord ends up bound to more than one action.
+    pub fn from_settings(settings: &KeymapSettings, preset: Option<&str>) -> Result<Self> {
+        let preset = preset.or(settings.preset.as_deref()).unwrap_or("default");
+        let mut keymap = Self::preset(preset)?;
+
+        for (id, keys) in &settings.bindings {
+            let action = Action::from_id(id).ok_or_else(|| anyhow!("Unknown action '{}' in keymap", id))?;
+            keymap.bindings.retain(|binding| binding.action != action);
+            for chord in keys.chords() {
+                let (code, modifiers) = parse_chord(chord)?;
+                keymap.bindings.push(Binding { code, modifiers, action });
+            }
+        }
+
+        let conflicts = keymap.conflicts();
+        if !conflicts.is_empty() {
+            bail!("Conflicting key bindings:\n  {}", conflicts.join("\n  "));
+        }
+        Ok(keymap)
+    }
+
+    /// Describe every key chord bound to more than one action
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        for (i, binding) in self.bindings.iter().enumerate() {
+            let earlier = self.bindings[..i]
+                .iter()
+                .find(|other| other.code == binding.code && other.modifiers == binding.modifiers);
+            if let Some(other) = earlier
+                && other.action != binding.action
+            {
+                conflicts.push(format!(
+                    "{} is bound to both '{}' and '{}'",
+                    binding.label(),
+                    other.action.id(),
+                    binding.action.id()
+                ));
+            }
+        }
+        conflicts
+    }
+
+    /// Find the action bound to a key event
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.iter().find(|binding| binding.matches(key)).map(|binding| binding.action)
+    }
+
+    /// Labels of all keys bound to an action
+    pub fn keys_for(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.action == action)
+            .map(Binding::label)
+            .collect()
+    }
+
+    /// Labels of the keys bound to an action joined with `/`, or `None` if unbound
+    pub fn key_hint(&self, action: Action) -> Option<String> {
+        let keys = self.keys_for(action);
+        (!keys.is_empty()).then(|| keys.join("/"))
+    }
+}
+
+/// Keys handled while typing into the search or command palette prompt
+pub const PROMPT_KEYS: &[(KeyCode, PromptCommand, &str)] = &[
+    (KeyCode::Enter, PromptCommand::Accept, "Accept"),
+    (KeyCode::Esc, PromptCommand::Cancel, "Cancel"),
+    (KeyCode::Up, PromptCommand::Previous, "Previous"),
+    (KeyCode::Down, PromptCommand::Next, "Next"),
+    (KeyCode::Backspace, PromptCommand::DeleteChar, "Delete"),
+];
+
+/// Keys handled while a popup is open
+pub const POPUP_KEYS: &[(KeyCode, PopupCommand, &str)] = &[
+    (KeyCode::Esc, PopupCommand::Close, "Close"),
+    (KeyCode::Enter, PopupCommand::Close, "Close"),
+    (KeyCode::Char('q'), PopupCommand::Close, "Close"),
+    (KeyCode::Up, PopupCommand::ScrollUp, "Scroll up"),
+    (KeyCode::Char('k'), PopupCommand::ScrollUp, "Scroll up"),
+    (KeyCode::Down, PopupCommand::ScrollDown, "Scroll down"),
+    (KeyCode::Char('j'), PopupCommand::ScrollDown, "Scroll down"),
+    (KeyCode::PageUp, PopupCommand::PageUp, "Page up"),
+    (KeyCode::PageDown, PopupCommand::PageDown, "Page down"),
+    (KeyCode::Left, PopupCommand::Previous, "Previous QR code"),
+    (KeyCode::Right, PopupCommand::Next, "Next QR code"),
+];
+
+/// Look up the prompt command for a key
+pub fn prompt_command(code: KeyCode) -> Option<PromptCommand> {
+    PROMPT_KEYS.iter().find(|(key, ..)| *key == code).map(|&(_, command, _)| command)
+}
+
+/// Keys handled on the error screen
+pub const ERROR_KEYS: &[(KeyCode, ErrorCommand, &str)] = &[
+    (KeyCode::Char('r'), ErrorCommand::Retry, "Retry"),
+    (KeyCode::Enter, ErrorCommand::Retry, "Retry"),
+    (KeyCode::Char('s'), ErrorCommand::SwitchSource, "Switch source"),
+    (KeyCode::Char('c'), ErrorCommand::UseCache, "Use cached data"),
+];
+
+/// Look up the error screen command for a key
+pub fn error_command(code: KeyCode) -> Option<ErrorCommand> {
+    ERROR_KEYS.iter().find(|(key, ..)| *key == code).map(|&(_, command, _)| command)
+}
+
+/// Look up the popup command for a key
+pub fn popup_command(code: KeyCode) -> Option<PopupCommand> {
+    POPUP_KEYS.iter().find(|(key, ..)| *key == code).map(|&(_, command, _)| command)
+}
+
+/// Group fixed key table entries by description, joining their key labels
+pub fn group_keys<C>(keys: &[(KeyCode, C, &'static str)]) -> Vec<(String, &'static str)> {
+    let mut groups: Vec<(String, &'static str)> = Vec::new();
+    for &(code, _, description) in keys {
+        let label = key_label(code, KeyModifiers::NONE);
+        match groups.iter_mut().find(|(_, existing)| *existing == description) {
+            Some((labels, _)) => {
+                labels.push('/');
+                labels.push_str(&label);
+            }
+            None => groups.push((label, description)),
+        }
+    }
+    groups
+}
+
+/// Parse a key chord such as `q`, `ctrl-p`, `C-x`, `alt-enter` or `shift-tab`
+pub fn parse_chord(chord: &str) -> Result<(KeyCode, KeyModifiers)> {
+    let invalid = || anyhow!("Invalid key chord '{}'", chord);
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = chord;
+
+    // A trailing '-' is the minus key itself, not a separator
+    while let Some((prefix, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+        modifiers |= match prefix.to_lowercase().as_str() {
+            "ctrl" | "control" | "c" => KeyModifiers::CONTROL,
+            "alt" | "meta" | "m" => KeyModifiers::ALT,
+            "shift" | "s" => KeyModifiers::SHIFT,
+            _ => return Err(invalid()),
+        };
+        rest = key;
+    }
+
+    let code = match rest.to_lowercase().as_str() {
+        _ if rest.chars().count() == 1 => KeyCode::Char(rest.chars().next().ok_or_else(invalid)?),
+        "space" => KeyCode::Char(' '),
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        key => match key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+            Some(n) if (1..=24).contains(&n) => KeyCode::F(n),
+            _ => return Err(invalid()),
+        },
+    };
+
+    // Terminals report Shift-Tab as BackTab and shifted letters as uppercase
+    let code = match code {
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::Char(c.to_ascii_uppercase()),
+        code => code,
+    };
+    Ok((code, normalize_modifiers(code, modifiers)))
+}
+
+/// Drop modifiers that are implied by the key code itself
+fn normalize_modifiers(code: KeyCode, modifiers: KeyModifiers) -> KeyModifiers {
+    match code {
+        KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+        _ => modifiers,
+    }
+}
+
+/// Display label for a key chord
+pub fn key_label(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let key = match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "Shift-Tab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    };
+
+    let mut label = String::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("Ctrl-");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("Alt-");
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        label.push_str("Shift-");
+    }
+    label.push_str(&key);
+    label
+}
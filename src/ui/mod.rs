@@ -1,5 +1,18 @@
+pub mod actions;
 pub mod app;
 pub mod ascii_art;
+pub mod color;
+pub mod icons;
+pub mod inline;
+pub mod input;
+pub mod keymap;
+pub mod mouse;
+pub mod palette;
+pub mod print;
+pub mod screenshot;
+pub mod search;
+pub mod terminal;
+pub mod theme;
 pub mod events;
 pub mod views;
 
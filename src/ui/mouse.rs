@@ -0,0 +1,71 @@
+//! Mouse hit testing
+//!
+//! Views record the screen regions of clickable elements in a [`HitMap`]
+//! while rendering. Mouse events are resolved against the regions of the
+//! last frame.
+
+use ratatui::layout::Rect;
+
+/// Something on screen that reacts to the mouse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HitTarget {
+    /// Section tab, by section index
+    Tab(usize),
+    /// Item of the current content section, by item index
+    Item(usize),
+    /// Link to open in the browser
+    Link(String),
+    /// Body of the open popup
+    Popup,
+}
+
+/// Clickable region of the screen
+#[derive(Debug, Clone)]
+pub struct Hit {
+    /// Region covered
+    pub area: Rect,
+    /// Target under the region
+    pub target: HitTarget,
+}
+
+/// Clickable regions of the last rendered frame and the hovered target
+#[derive(Debug, Clone, Default)]
+pub struct HitMap {
+    /// Regions in render order, later regions drawn on top
+    hits: Vec<Hit>,
+    /// Target under the mouse pointer
+    pub hovered: Option<HitTarget>,
+}
+
+impl HitMap {
+    /// Forget the regions of the previous frame
+    pub fn clear(&mut self) {
+        self.hits.clear();
+    }
+
+    /// Record a clickable region
+    pub fn push(&mut self, area: Rect, target: HitTarget) {
+        if area.width > 0 && area.height > 0 {
+            self.hits.push(Hit { area, target });
+        }
+    }
+
+    /// Topmost target at a screen position
+    pub fn target_at(&self, column: u16, row: u16) -> Option<&HitTarget> {
+        self.hits
+            .iter()
+            .rev()
+            .find(|hit| {
+                column >= hit.area.x
+                    && column < hit.area.x + hit.area.width
+                    && row >= hit.area.y
+                    && row < hit.area.y + hit.area.height
+            })
+            .map(|hit| &hit.target)
+    }
+
+    /// Whether the mouse pointer is over a target
+    pub fn is_hovered(&self, target: &HitTarget) -> bool {
+        self.hovered.as_ref() == Some(target)
+    }
+}
@@ -0,0 +1,81 @@
+//! Command palette listing every action
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use crate::ui::actions::Action;
+
+/// An action offered in the palette
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    /// Action to perform
+    pub action: Action,
+    /// Display name
+    pub name: String,
+    /// Keys bound to the action
+    pub keys: Vec<String>,
+}
+
+/// Command palette state
+#[derive(Default)]
+pub struct Palette {
+    /// Whether the palette is open
+    pub active: bool,
+    /// Filter query
+    pub query: String,
+    /// All available entries
+    entries: Vec<PaletteEntry>,
+    /// Entries matching the query, with matched character indices
+    pub filtered: Vec<(PaletteEntry, Vec<usize>)>,
+    /// Selected entry in `filtered`
+    pub selected: usize,
+}
+
+impl Palette {
+    /// Open the palette with the given entries
+    pub fn open(&mut self, entries: Vec<PaletteEntry>) {
+        self.active = true;
+        self.query.clear();
+        self.entries = entries;
+        self.filter();
+    }
+
+    /// Close the palette
+    pub fn close(&mut self) {
+        self.active = false;
+        self.entries.clear();
+        self.filtered.clear();
+    }
+
+    /// Re-filter entries against the query
+    pub fn filter(&mut self) {
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let mut filtered: Vec<(i64, PaletteEntry, Vec<usize>)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                if self.query.is_empty() {
+                    return Some((0, entry.clone(), Vec::new()));
+                }
+                let (score, indices) = matcher.fuzzy_indices(&entry.name, &self.query)?;
+                Some((score, entry.clone(), indices))
+            })
+            .collect();
+        filtered.sort_by_key(|(score, ..)| std::cmp::Reverse(*score));
+        self.filtered = filtered.into_iter().map(|(_, entry, indices)| (entry, indices)).collect();
+        self.selected = 0;
+    }
+
+    /// Move the selection by `delta`, wrapping around
+    pub fn select_relative(&mut self, delta: isize) {
+        if !self.filtered.is_empty() {
+            let len = self.filtered.len() as isize;
+            self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Action of the selected entry
+    pub fn current(&self) -> Option<Action> {
+        self.filtered.get(self.selected).map(|(entry, _)| entry.action)
+    }
+}
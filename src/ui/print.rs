@@ -0,0 +1,246 @@
+//! Non-interactive printing
+//!
+//! Writes the portfolio, or a single section of it, to stdout as plain or
+//! ANSI-styled text for MOTD banners, shell startup scripts and pagers. The
+//! text is laid out like the viewer shows it, using the active theme.
+
+use anyhow::{anyhow, Result};
+use crossterm::style::{Attribute, ContentStyle, PrintStyledContent, StyledContent};
+use crossterm::QueueableCommand;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::io::{self, IsTerminal, Write};
+use textwrap::wrap;
+
+use crate::config::args::{ColorChoice, PrintArgs};
+use crate::config::Args;
+use crate::data;
+use crate::processor::formatter::{FormattedContentItem, FormattedContentSection, FormattedPortfolio, TextCache};
+use crate::processor::Formatter;
+use crate::ui::icons::{IconMode, Icons};
+use crate::ui::theme::{Theme, ThemeSet};
+use crate::ui::views::detail;
+
+/// Width used when stdout is not a terminal and no width is given
+const DEFAULT_WIDTH: usize = 80;
+
+/// Narrowest width text is wrapped to
+const MIN_WIDTH: usize = 20;
+
+/// Part of the portfolio to print
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Page {
+    /// Name, contacts and about text
+    Home,
+    /// Content section by index
+    Section(usize),
+}
+
+/// Load the portfolio and print the requested section
+pub async fn print(args: &Args, print: &PrintArgs, themes: &ThemeSet) -> Result<()> {
+    let stdout = io::stdout();
+    let terminal = stdout.is_terminal();
+    let width = match print.width {
+        Some(width) => width,
+        None if terminal => crossterm::terminal::size().map_or(DEFAULT_WIDTH, |(width, _)| width as usize),
+        None => DEFAULT_WIDTH,
+    }
+    .max(MIN_WIDTH);
+    let color = match print.color {
+        ColorChoice::Auto => terminal,
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+
+    let portfolio = data::load(&args.config_path).await?;
+    let theme = themes.select(args.theme.as_deref(), portfolio.darkmode.unwrap_or(true))?;
+    let formatted = Formatter::with_max_width(width).format(&portfolio);
+    let icons = Icons::new(IconMode::from_flags(args.ascii, args.nerd_fonts));
+
+    let pages = match print.section {
+        Some(ref query) => vec![find_page(&formatted, query)?],
+        None => all_pages(&formatted),
+    };
+    let lines = pages_lines(&formatted, &pages, &icons, width, theme);
+
+    let mut out = stdout.lock();
+    let result = lines
+        .iter()
+        .try_for_each(|line| write_line(&mut out, line, color))
+        .and_then(|()| out.flush());
+    match result {
+        // The reader went away, e.g. `head` or a pager that was closed
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Find a section by navigation index or by title, ignoring case
+///
+/// An exact title match wins over a title that only starts with `query`.
+pub fn find_page(portfolio: &FormattedPortfolio, query: &str) -> Result<Page> {
+    let sections = &portfolio.content_sections;
+    if let Ok(index) = query.parse::<usize>() {
+        return match index {
+            0 => Ok(Page::Home),
+            index if index <= sections.len() => Ok(Page::Section(index - 1)),
+            _ => Err(anyhow!("There is no section {}, the last one is {}", index, sections.len())),
+        };
+    }
+
+    let lower = query.to_lowercase();
+    if lower == "home" {
+        return Ok(Page::Home);
+    }
+    sections
+        .iter()
+        .position(|section| section.title.to_lowercase() == lower)
+        .or_else(|| sections.iter().position(|section| section.title.to_lowercase().starts_with(&lower)))
+        .map(Page::Section)
+        .ok_or_else(|| {
+            let titles: Vec<&str> = sections.iter().map(|section| section.title.as_str()).collect();
+            anyhow!("Unknown section '{}', expected Home or one of: {}", query, titles.join(", "))
+        })
+}
+
+/// Home followed by every content section
+pub fn all_pages(portfolio: &FormattedPortfolio) -> Vec<Page> {
+    std::iter::once(Page::Home)
+        .chain((0..portfolio.content_sections.len()).map(Page::Section))
+        .collect()
+}
+
+/// Lines of several pages, separated by blank lines
+pub fn pages_lines(
+    portfolio: &FormattedPortfolio,
+    pages: &[Page],
+    icons: &Icons,
+    width: usize,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (i, &page) in pages.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        match page {
+            Page::Home => lines.extend(home_lines(portfolio, icons, width, theme)),
+            Page::Section(index) => {
+                lines.extend(section_lines(&portfolio.content_sections[index], icons, width, theme))
+            }
+        }
+    }
+    lines
+}
+
+/// Name, title, contacts and about text
+fn home_lines(portfolio: &FormattedPortfolio, icons: &Icons, width: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(portfolio.name.clone(), theme.heading),
+            Span::raw(" "),
+            Span::styled(portfolio.title.clone(), theme.subtitle),
+        ]),
+        Line::from(""),
+    ];
+
+    let links = portfolio
+        .social
+        .iter()
+        .map(|social| {
+            let address = if social.platform == "Email" { &social.username } else { &social.url };
+            (icons.label(&social.platform, &social.platform), address.clone())
+        })
+        .chain(portfolio.links.iter().map(|link| (icons.label(&link.icon, &link.title), link.url.clone())));
+    for (label, url) in links {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", label), theme.link),
+            Span::styled(url, theme.text),
+        ]));
+    }
+
+    if !portfolio.about.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(heading(icons.label("user", "About Me"), width, theme));
+        for paragraph in portfolio.about.split('\n') {
+            lines.extend(
+                wrap(paragraph, width)
+                    .into_iter()
+                    .map(|line| Line::from(Span::styled(line.into_owned(), theme.text))),
+            );
+        }
+    }
+    lines
+}
+
+/// Section heading and every item in full
+fn section_lines(section: &FormattedContentSection, icons: &Icons, width: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = heading(icons.section_label(&section.title), width, theme).to_vec();
+    for (i, item) in section.items.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.extend(item_lines(item, icons, width, theme));
+    }
+    lines
+}
+
+/// Item title and everything about the item
+pub fn item_lines(item: &FormattedContentItem, icons: &Icons, width: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if !item.title.is_empty() {
+        lines.push(Line::from(Span::styled(icons.label(&item.icon, &item.title), theme.title)));
+    }
+    lines.extend(detail::item_lines(item, icons, width, theme, &mut TextCache::default()));
+    lines
+}
+
+/// Text of a line without its styles
+pub fn plain(line: &Line) -> String {
+    line.spans.iter().map(|span| span.content.as_ref()).collect()
+}
+
+/// Heading text over a separator as wide as the output
+fn heading(text: String, width: usize, theme: &Theme) -> [Line<'static>; 2] {
+    [
+        Line::from(Span::styled(text, theme.heading)),
+        Line::from(Span::styled("─".repeat(width), theme.muted)),
+    ]
+}
+
+/// Write a line, with ANSI styles if `color` is set
+fn write_line(out: &mut impl Write, line: &Line, color: bool) -> io::Result<()> {
+    for span in &line.spans {
+        if color {
+            let style = line.style.patch(span.style);
+            out.queue(PrintStyledContent(StyledContent::new(content_style(style), span.content.as_ref())))?;
+        } else {
+            out.write_all(span.content.as_bytes())?;
+        }
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Convert a ratatui style to a crossterm one
+pub fn content_style(style: Style) -> ContentStyle {
+    let mut content = ContentStyle {
+        foreground_color: style.fg.map(Into::into),
+        background_color: style.bg.map(Into::into),
+        underline_color: style.underline_color.map(Into::into),
+        ..ContentStyle::default()
+    };
+    for (modifier, attribute) in [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            content.attributes.set(attribute);
+        }
+    }
+    content
+}
@@ -0,0 +1,276 @@
+//! Screenshots
+//!
+//! Renders a view of the viewer into an off-screen buffer and serializes the
+//! cells as an SVG image, an HTML `<pre>` block or ANSI text. Used by the
+//! `screenshot` command and the in-app screenshot key.
+
+use anyhow::{Context, Result};
+use crossterm::style::{PrintStyledContent, ResetColor, StyledContent};
+use crossterm::QueueableCommand;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Modifier, Style};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::args::{ScreenshotArgs, ScreenshotFormat};
+use crate::config::Args;
+use crate::data;
+use crate::processor::Formatter;
+use crate::ui::app::App;
+use crate::ui::color;
+use crate::ui::keymap::Keymap;
+use crate::ui::print::{self, Page};
+use crate::ui::theme::{Theme, ThemeSet};
+
+/// Font size of the SVG text in pixels
+const FONT_SIZE: f32 = 14.0;
+
+/// Width of a character cell in pixels, 0.6em as in most monospace fonts
+const CELL_WIDTH: f32 = 8.4;
+
+/// Height of a character cell in pixels
+const CELL_HEIGHT: f32 = 17.0;
+
+/// Distance from the top of a cell to the text baseline in pixels
+const BASELINE: f32 = 13.0;
+
+/// Fonts tried for the SVG and HTML output
+const FONT_FAMILY: &str = "ui-monospace, 'DejaVu Sans Mono', Menlo, Consolas, monospace";
+
+/// Load the portfolio, render the requested view and write the screenshot
+pub async fn screenshot(args: &Args, shot: &ScreenshotArgs, keymap: Keymap, themes: ThemeSet) -> Result<()> {
+    let portfolio = data::load(&args.config_path).await?;
+    let section = match shot.section {
+        Some(ref query) => match print::find_page(&Formatter::new().format(&portfolio), query)? {
+            Page::Home => 0,
+            Page::Section(index) => index + 1,
+        },
+        None => 0,
+    };
+    let mut app = App::headless(args.clone(), keymap, themes)?;
+    app.show(portfolio, section);
+    let (width, height) = shot.size;
+    let buffer = app.screenshot(width, height)?;
+    let output = render(&buffer, shot.format, app.theme());
+
+    match shot.output {
+        Some(ref path) => fs::write(path, output).with_context(|| format!("Could not write {}", path))?,
+        None => io::stdout().write_all(output.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Serialize a buffer in the given format
+///
+/// Cells with the terminal's default colors get the colors of `theme`.
+pub fn render(buffer: &Buffer, format: ScreenshotFormat, theme: &Theme) -> String {
+    match format {
+        ScreenshotFormat::Svg => svg(buffer, theme),
+        ScreenshotFormat::Html => html(buffer, theme),
+        ScreenshotFormat::Ansi => ansi(buffer),
+    }
+}
+
+/// Cells of one row with the same style
+struct Run {
+    style: Style,
+    text: String,
+    /// First column
+    column: u16,
+    /// Width in columns
+    width: u16,
+}
+
+/// Split a row into runs of equally styled cells
+///
+/// Cells hidden behind a wide character are skipped, so the text of a run
+/// is what the terminal would show.
+fn runs(buffer: &Buffer, y: u16) -> Vec<Run> {
+    let area = buffer.area;
+    let mut runs: Vec<Run> = Vec::new();
+    let mut x = area.left();
+    while x < area.right() {
+        let cell = buffer.get(x, y);
+        let symbol = if cell.symbol().is_empty() { " " } else { cell.symbol() };
+        let width = (symbol.width() as u16).clamp(1, area.right() - x);
+        let style = cell.style();
+        match runs.last_mut() {
+            Some(run) if run.style == style => {
+                run.text.push_str(symbol);
+                run.width += width;
+            }
+            _ => runs.push(Run {
+                style,
+                text: symbol.to_string(),
+                column: x,
+                width,
+            }),
+        }
+        x += width;
+    }
+    runs
+}
+
+/// Foreground and background colors of a style as hex, after reversing
+fn colors(style: Style, theme: &Theme) -> (String, String) {
+    let default_fg = theme.text.fg.and_then(color::rgb).unwrap_or((229, 229, 229));
+    let default_bg = color::rgb(theme.background).unwrap_or((0, 0, 0));
+    let fg = style.fg.and_then(color::rgb).unwrap_or(default_fg);
+    let bg = style.bg.and_then(color::rgb).unwrap_or(default_bg);
+    if style.add_modifier.contains(Modifier::REVERSED) {
+        (hex(bg), hex(fg))
+    } else {
+        (hex(fg), hex(bg))
+    }
+}
+
+/// CSS hex notation of a color
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Pixel value with at most two decimals
+fn px(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Escape text for XML and HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// SVG image with one rectangle per background run and one text element
+/// per text run, each stretched to the width of its cells
+fn svg(buffer: &Buffer, theme: &Theme) -> String {
+    let area = buffer.area;
+    let width = px(area.width as f32 * CELL_WIDTH);
+    let height = px(area.height as f32 * CELL_HEIGHT);
+    let background = colors(Style::default(), theme).1;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(out, r#"<rect width="100%" height="100%" fill="{}"/>"#, background);
+    let _ = writeln!(
+        out,
+        r#"<g font-family="{}" font-size="{}" xml:space="preserve">"#,
+        FONT_FAMILY, FONT_SIZE
+    );
+    for y in area.top()..area.bottom() {
+        let top = (y - area.top()) as f32 * CELL_HEIGHT;
+        let text_y = px(top + BASELINE);
+        let top = px(top);
+        for run in runs(buffer, y) {
+            let (fg, bg) = colors(run.style, theme);
+            let x = px((run.column - area.left()) as f32 * CELL_WIDTH);
+            let run_width = px(run.width as f32 * CELL_WIDTH);
+            if bg != background {
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{x}" y="{top}" width="{run_width}" height="{CELL_HEIGHT}" fill="{bg}"/>"#
+                );
+            }
+            if run.text.trim().is_empty() {
+                continue;
+            }
+
+            let modifiers = run.style.add_modifier;
+            let mut attributes = format!(r#" fill="{}""#, fg);
+            if modifiers.contains(Modifier::BOLD) {
+                attributes.push_str(r#" font-weight="bold""#);
+            }
+            if modifiers.contains(Modifier::ITALIC) {
+                attributes.push_str(r#" font-style="italic""#);
+            }
+            if modifiers.contains(Modifier::DIM) {
+                attributes.push_str(r#" opacity="0.6""#);
+            }
+            match (modifiers.contains(Modifier::UNDERLINED), modifiers.contains(Modifier::CROSSED_OUT)) {
+                (true, _) => attributes.push_str(r#" text-decoration="underline""#),
+                (false, true) => attributes.push_str(r#" text-decoration="line-through""#),
+                (false, false) => {}
+            }
+            let _ = writeln!(
+                out,
+                r#"<text x="{}" y="{}" textLength="{}" lengthAdjust="spacingAndGlyphs"{}>{}</text>"#,
+                x,
+                text_y,
+                run_width,
+                attributes,
+                escape(&run.text)
+            );
+        }
+    }
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+/// HTML page with the screen as a `<pre>` block of styled spans
+fn html(buffer: &Buffer, theme: &Theme) -> String {
+    let area = buffer.area;
+    let (foreground, background) = colors(Style::default(), theme);
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>portfolio-tui</title>\n</head>\n");
+    let _ = writeln!(out, r#"<body style="margin: 0; background: {};">"#, background);
+    let _ = write!(
+        out,
+        r#"<pre style="margin: 0; padding: 1em; font-family: {}; font-size: {}px; line-height: {}px; color: {}; background: {};">"#,
+        FONT_FAMILY.replace('\'', "&quot;"),
+        FONT_SIZE,
+        CELL_HEIGHT,
+        foreground,
+        background
+    );
+    for y in area.top()..area.bottom() {
+        for run in runs(buffer, y) {
+            let (fg, bg) = colors(run.style, theme);
+            let modifiers = run.style.add_modifier;
+            let mut style = String::new();
+            if fg != foreground {
+                let _ = write!(style, "color: {}; ", fg);
+            }
+            if bg != background {
+                let _ = write!(style, "background: {}; ", bg);
+            }
+            for (modifier, css) in [
+                (Modifier::BOLD, "font-weight: bold; "),
+                (Modifier::ITALIC, "font-style: italic; "),
+                (Modifier::DIM, "opacity: 0.6; "),
+                (Modifier::UNDERLINED, "text-decoration: underline; "),
+                (Modifier::CROSSED_OUT, "text-decoration: line-through; "),
+            ] {
+                if modifiers.contains(modifier) {
+                    style.push_str(css);
+                }
+            }
+            if style.is_empty() {
+                out.push_str(&escape(&run.text));
+            } else {
+                let _ = write!(out, r#"<span style="{}">{}</span>"#, style.trim_end(), escape(&run.text));
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+/// Text with ANSI escape sequences, one line per row
+fn ansi(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out: Vec<u8> = Vec::new();
+    for y in area.top()..area.bottom() {
+        for run in runs(buffer, y) {
+            let _ = out.queue(PrintStyledContent(StyledContent::new(print::content_style(run.style), run.text)));
+        }
+        let _ = out.queue(ResetColor);
+        out.push(b'\n');
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
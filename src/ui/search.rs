@@ -0,0 +1,161 @@
+//! Fuzzy search across the formatted portfolio
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use crate::processor::formatter::FormattedPortfolio;
+
+/// Maximum number of results kept for a query
+const MAX_RESULTS: usize = 50;
+
+/// Where a searchable piece of text lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchTarget {
+    /// Index into the navigation sections (0 is Home)
+    pub section: usize,
+    /// Item within the content section, if any
+    pub item: Option<usize>,
+}
+
+/// A searchable piece of text
+#[derive(Debug, Clone)]
+struct SearchEntry {
+    target: SearchTarget,
+    breadcrumb: String,
+    text: String,
+}
+
+/// A ranked search result
+#[derive(Debug, Clone)]
+pub struct SearchMatch {
+    /// Where the match was found
+    pub target: SearchTarget,
+    /// Section, item and field the match came from
+    pub breadcrumb: String,
+    /// Matched text on a single line
+    pub text: String,
+    /// Character indices of the matched characters in `text`
+    pub indices: Vec<usize>,
+    /// Match score, higher is better
+    pub score: i64,
+}
+
+/// Index of all searchable text in a portfolio
+#[derive(Default)]
+pub struct SearchIndex {
+    entries: Vec<SearchEntry>,
+}
+
+impl SearchIndex {
+    /// Build an index from a formatted portfolio
+    pub fn new(portfolio: &FormattedPortfolio) -> Self {
+        let mut entries = Vec::new();
+        let home = SearchTarget { section: 0, item: None };
+        push_entry(&mut entries, home, "Home › Name", &portfolio.name);
+        push_entry(&mut entries, home, "Home › Title", &portfolio.title);
+        push_entry(&mut entries, home, "Home › About", &portfolio.about);
+
+        for (i, section) in portfolio.content_sections.iter().enumerate() {
+            let section_target = SearchTarget { section: i + 1, item: None };
+            push_entry(&mut entries, section_target, &section.title, &section.title);
+
+            for (j, item) in section.items.iter().enumerate() {
+                let target = SearchTarget { section: i + 1, item: Some(j) };
+                let crumb = if item.title.is_empty() {
+                    section.title.clone()
+                } else {
+                    format!("{} › {}", section.title, item.title)
+                };
+                push_entry(&mut entries, target, &crumb, &item.title);
+                push_entry(&mut entries, target, &format!("{} › Subtitle", crumb), &item.sub_title);
+                push_entry(&mut entries, target, &format!("{} › Caption", crumb), &item.caption);
+                push_entry(&mut entries, target, &format!("{} › Description", crumb), &item.description);
+                push_entry(&mut entries, target, &format!("{} › Quote", crumb), &item.quote);
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Find the best matches for a query
+    pub fn search(&self, query: &str) -> Vec<SearchMatch> {
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let matcher = SkimMatcherV2::default().smart_case();
+        let mut matches: Vec<SearchMatch> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let (score, indices) = matcher.fuzzy_indices(&entry.text, query)?;
+                Some(SearchMatch {
+                    target: entry.target,
+                    breadcrumb: entry.breadcrumb.clone(),
+                    text: entry.text.clone(),
+                    indices,
+                    score,
+                })
+            })
+            .collect();
+
+        // Stable sort keeps document order for equal scores
+        matches.sort_by_key(|result| std::cmp::Reverse(result.score));
+        matches.truncate(MAX_RESULTS);
+        matches
+    }
+}
+
+/// Add an entry, flattening the text to a single line
+fn push_entry(entries: &mut Vec<SearchEntry>, target: SearchTarget, breadcrumb: &str, text: &str) {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !text.is_empty() {
+        entries.push(SearchEntry {
+            target,
+            breadcrumb: breadcrumb.to_string(),
+            text,
+        });
+    }
+}
+
+/// Interactive search state
+#[derive(Default)]
+pub struct Search {
+    /// Whether the search prompt is open
+    pub active: bool,
+    /// Current query
+    pub query: String,
+    /// Results for the current query
+    pub results: Vec<SearchMatch>,
+    /// Selected result
+    pub selected: usize,
+}
+
+impl Search {
+    /// Open the search prompt with an empty query
+    pub fn open(&mut self) {
+        self.active = true;
+        self.query.clear();
+        self.results.clear();
+        self.selected = 0;
+    }
+
+    /// Re-run the query against the index
+    pub fn update(&mut self, index: &SearchIndex) {
+        self.results = index.search(&self.query);
+        self.selected = 0;
+    }
+
+    /// Move the selection by `delta`, wrapping around
+    pub fn select_relative(&mut self, delta: isize) {
+        if !self.results.is_empty() {
+            let len = self.results.len() as isize;
+            self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Currently selected result
+    pub fn current(&self) -> Option<&SearchMatch> {
+        self.results.get(self.selected)
+    }
+}
@@ -0,0 +1,324 @@
+//! Terminal lifecycle
+//!
+//! A [`TerminalSession`] owns the terminal while the application runs. It
+//! switches to raw mode and the alternate screen when created and restores
+//! the terminal when dropped, on early returns, on panics and around a
+//! suspend to the shell.
+//!
+//! A session can also draw on a remote terminal, such as an SSH channel. Its
+//! output goes through a [`RemoteOutput`] and the terminal is set up and
+//! restored with escape sequences instead of the local tty.
+
+use anyhow::Result;
+use base64::Engine;
+use crossterm::cursor::Show;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
+use ratatui::{Frame, Terminal, TerminalOptions, Viewport};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Once};
+use tokio::sync::mpsc;
+
+/// Installs the panic hook only once, however many sessions are created
+static PANIC_HOOK: Once = Once::new();
+
+/// Fullscreen terminal owned by the application
+pub struct TerminalSession {
+    terminal: Terminal<CrosstermBackend<Box<dyn Write + Send>>>,
+    /// Mouse capture is enabled
+    mouse: bool,
+    /// Output of a terminal on the other end of a connection, kept to draw
+    /// on it again at a new size; `None` for this process's tty
+    remote: Option<RemoteOutput>,
+}
+
+impl TerminalSession {
+    /// Take over the terminal, capturing the mouse if `mouse` is set
+    pub fn enter(mouse: bool) -> Result<Self> {
+        // Put the terminal back before a panic message is printed
+        PANIC_HOOK.call_once(|| {
+            let previous_hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                restore(mouse);
+                previous_hook(info);
+            }));
+        });
+
+        setup(mouse)?;
+        let output: Box<dyn Write + Send> = Box::new(io::stdout());
+        let terminal = Terminal::new(CrosstermBackend::new(output))?;
+        Ok(Self { terminal, mouse, remote: None })
+    }
+
+    /// Draw on a remote terminal of the given size through `output`
+    ///
+    /// The size cannot be queried, so it must be updated with [`resize`](Self::resize).
+    pub fn remote(output: RemoteOutput, size: (u16, u16), mouse: bool) -> Result<Self> {
+        let mut terminal = remote_terminal(output.clone(), size)?;
+        let backend = terminal.backend_mut();
+        backend.execute(EnterAlternateScreen)?;
+        if mouse {
+            backend.execute(EnableMouseCapture)?;
+        }
+        Ok(Self { terminal, mouse, remote: Some(output) })
+    }
+
+    /// Whether the terminal is remote
+    pub fn is_remote(&self) -> bool {
+        self.remote.is_some()
+    }
+
+    /// Follow a new terminal size
+    ///
+    /// Local terminals are resized automatically on the next draw. Remote
+    /// ones get a new viewport and are cleared.
+    pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
+        if let Some(ref output) = self.remote {
+            self.terminal = remote_terminal(output.clone(), (width, height))?;
+            self.terminal.clear()?;
+        }
+        Ok(())
+    }
+
+    /// Copy text to the clipboard of the terminal with an OSC 52 sequence
+    pub fn copy(&mut self, text: &str) -> Result<()> {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+        let output = self.terminal.backend_mut();
+        write!(output, "\x1b]52;c;{}\x07", encoded)?;
+        output.flush()?;
+        Ok(())
+    }
+
+    /// Draw a frame
+    ///
+    /// Remote terminals that missed frames are cleared first, so the whole
+    /// screen is drawn again.
+    pub fn draw(&mut self, render: impl FnOnce(&mut Frame)) -> Result<()> {
+        if self.remote.as_ref().is_some_and(RemoteOutput::take_stale) {
+            self.terminal.clear()?;
+        }
+        self.terminal.draw(render)?;
+        Ok(())
+    }
+
+    /// Hand the terminal back to the shell and stop the process
+    ///
+    /// Returns once the process is continued with `SIGCONT` (e.g. by `fg`),
+    /// after taking the terminal over again. The next draw repaints the whole
+    /// screen.
+    ///
+    /// Remote sessions cannot be suspended and are left alone.
+    #[cfg(unix)]
+    pub fn suspend(&mut self) -> Result<()> {
+        if self.is_remote() {
+            return Ok(());
+        }
+        restore(self.mouse);
+        // SIGSTOP cannot be caught, so the process stops right here
+        // SAFETY: raising a signal for the current process has no memory effects
+        unsafe {
+            libc::raise(libc::SIGSTOP);
+        }
+        setup(self.mouse)?;
+        self.terminal.clear()?;
+        Ok(())
+    }
+
+    /// Suspending is not supported on this platform
+    #[cfg(not(unix))]
+    pub fn suspend(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        if !self.is_remote() {
+            restore(self.mouse);
+            return;
+        }
+        let output = self.terminal.backend_mut();
+        if self.mouse {
+            let _ = output.execute(DisableMouseCapture);
+        }
+        let _ = output.execute(LeaveAlternateScreen);
+        let _ = output.execute(Show);
+    }
+}
+
+/// Most bytes held for a slow remote terminal before they are dropped and the
+/// screen is drawn again from scratch
+const MAX_BACKLOG: usize = 1 << 20;
+
+/// Output of a remote terminal
+///
+/// Writes are collected and each flush, one per drawn frame, hands them to
+/// the [`RemoteFrames`] that deliver them to the terminal. Frames that arrive
+/// while the terminal is still busy are merged and sent as one; past
+/// [`MAX_BACKLOG`] they are dropped and [`TerminalSession`] repaints the whole
+/// screen. Clones share the same frames, which end once every clone is
+/// dropped.
+pub struct RemoteOutput {
+    buffer: Vec<u8>,
+    shared: Arc<Backlog>,
+    /// Wakes the receiver, holding at most one pending signal
+    ready: mpsc::Sender<()>,
+}
+
+/// Bytes flushed but not yet taken by the receiver
+#[derive(Default)]
+struct Backlog {
+    bytes: Mutex<Vec<u8>>,
+    /// Bytes were dropped since the last draw
+    stale: AtomicBool,
+}
+
+/// Frames of a [`RemoteOutput`], to send to the terminal
+pub struct RemoteFrames {
+    shared: Arc<Backlog>,
+    ready: mpsc::Receiver<()>,
+}
+
+impl RemoteOutput {
+    /// Create an output and the receiver of its frames
+    pub fn new() -> (Self, RemoteFrames) {
+        let shared = Arc::new(Backlog::default());
+        let (ready, receiver) = mpsc::channel(1);
+        let output = Self { buffer: Vec::new(), shared: shared.clone(), ready };
+        (output, RemoteFrames { shared, ready: receiver })
+    }
+
+    /// Whether frames were dropped since the last call
+    fn take_stale(&self) -> bool {
+        self.shared.stale.swap(false, Ordering::Relaxed)
+    }
+}
+
+impl Clone for RemoteOutput {
+    fn clone(&self) -> Self {
+        Self { buffer: Vec::new(), shared: self.shared.clone(), ready: self.ready.clone() }
+    }
+}
+
+impl Write for RemoteOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        if self.ready.is_closed() {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "The remote terminal is gone"));
+        }
+        {
+            let mut backlog = self.shared.bytes.lock().unwrap();
+            if backlog.len() + self.buffer.len() > MAX_BACKLOG {
+                backlog.clear();
+                self.buffer.clear();
+                self.shared.stale.store(true, Ordering::Relaxed);
+                return Ok(());
+            }
+            backlog.append(&mut self.buffer);
+        }
+        // A full channel already has a wake-up pending for these bytes
+        let _ = self.ready.try_send(());
+        Ok(())
+    }
+}
+
+impl RemoteFrames {
+    /// Wait for the bytes of the frames drawn since the last call
+    ///
+    /// Returns `None` once every [`RemoteOutput`] is dropped and everything
+    /// was taken.
+    pub async fn recv(&mut self) -> Option<Vec<u8>> {
+        loop {
+            let closed = self.ready.recv().await.is_none();
+            let frame = std::mem::take(&mut *self.shared.bytes.lock().unwrap());
+            if !frame.is_empty() {
+                return Some(frame);
+            }
+            if closed {
+                return None;
+            }
+        }
+    }
+}
+
+/// Terminal drawing through `output` with a fixed size
+fn remote_terminal(output: RemoteOutput, (width, height): (u16, u16)) -> Result<Terminal<CrosstermBackend<Box<dyn Write + Send>>>> {
+    let output: Box<dyn Write + Send> = Box::new(output);
+    let viewport = Viewport::Fixed(Rect::new(0, 0, width, height));
+    Ok(Terminal::with_options(CrosstermBackend::new(output), TerminalOptions { viewport })?)
+}
+
+/// Enter raw mode and the alternate screen
+fn setup(mouse: bool) -> Result<()> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    stdout.execute(EnterAlternateScreen)?;
+    if mouse {
+        stdout.execute(EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
+/// Leave raw mode and the alternate screen, ignoring errors
+///
+/// The terminal may already be gone, for example after `SIGHUP`.
+fn restore(mouse: bool) {
+    let mut stdout = io::stdout();
+    if mouse {
+        let _ = stdout.execute(DisableMouseCapture);
+    }
+    let _ = disable_raw_mode();
+    let _ = stdout.execute(LeaveAlternateScreen);
+    let _ = stdout.execute(Show);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn frames_drawn_while_busy_are_sent_together() {
+        let (mut output, mut frames) = RemoteOutput::new();
+        for frame in [&b"one"[..], b"two", b"three"] {
+            output.write_all(frame).unwrap();
+            output.flush().unwrap();
+        }
+        assert_eq!(frames.recv().await.unwrap(), b"onetwothree");
+        drop(output);
+        assert_eq!(frames.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn an_overlong_backlog_is_dropped_for_a_repaint() {
+        let (mut output, mut frames) = RemoteOutput::new();
+        output.write_all(&vec![b'x'; MAX_BACKLOG]).unwrap();
+        output.flush().unwrap();
+        output.write_all(b"more").unwrap();
+        output.flush().unwrap();
+        assert!(output.take_stale());
+        assert!(!output.take_stale());
+
+        output.write_all(b"repaint").unwrap();
+        output.flush().unwrap();
+        assert_eq!(frames.recv().await.unwrap(), b"repaint");
+    }
+
+    #[test]
+    fn flushing_fails_once_the_terminal_is_gone() {
+        let (mut output, frames) = RemoteOutput::new();
+        drop(frames);
+        output.write_all(b"frame").unwrap();
+        assert_eq!(output.flush().unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    }
+}
@@ -0,0 +1,300 @@
+//! Color themes
+//!
+//! Views style text through the semantic styles of a [`Theme`] instead of
+//! fixed colors. Dark, light and high-contrast themes are built in, and
+//! custom themes are read from TOML or YAML files in
+//! `<config dir>/portfolio-tui/themes/`. Themes are reduced to the colors
+//! the terminal supports when they are loaded.
+
+use anyhow::{anyhow, Context, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::ui::color::ColorDepth;
+
+/// Semantic styles used by the views
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Theme name
+    pub name: String,
+    /// Background color of the whole screen
+    pub background: Color,
+    /// Body text
+    pub text: Style,
+    /// Section headings, names and popup titles
+    pub heading: Style,
+    /// Item titles
+    pub title: Style,
+    /// Item subtitles and captions
+    pub subtitle: Style,
+    /// Keys, prompts, search matches and status messages
+    pub accent: Style,
+    /// Separators, URLs and other secondary text
+    pub muted: Style,
+    /// Link labels
+    pub link: Style,
+    /// Selected tab or list entry
+    pub highlight: Style,
+    /// Popup borders
+    pub border: Style,
+    /// Error messages
+    pub error: Style,
+}
+
+impl Theme {
+    /// Built-in dark theme
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            background: Color::Black,
+            text: Style::default().fg(Color::White),
+            heading: Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            title: Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            subtitle: Style::default().fg(Color::Yellow),
+            accent: Style::default().fg(Color::Yellow),
+            muted: Style::default().fg(Color::DarkGray),
+            link: Style::default().fg(Color::Blue),
+            highlight: Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD),
+            border: Style::default().fg(Color::Cyan),
+            error: Style::default().fg(Color::Red),
+        }
+    }
+
+    /// Built-in light theme
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            background: Color::White,
+            text: Style::default().fg(Color::Black),
+            heading: Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD),
+            title: Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            subtitle: Style::default().fg(Color::Magenta),
+            accent: Style::default().fg(Color::Red),
+            muted: Style::default().fg(Color::DarkGray),
+            link: Style::default().fg(Color::Blue),
+            highlight: Style::default().fg(Color::White).bg(Color::Blue).add_modifier(Modifier::BOLD),
+            border: Style::default().fg(Color::Blue),
+            error: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// Built-in high-contrast theme
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            background: Color::Black,
+            text: Style::default().fg(Color::White),
+            heading: Style::default().fg(Color::LightCyan).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            title: Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+            subtitle: Style::default().fg(Color::LightYellow),
+            accent: Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+            muted: Style::default().fg(Color::Gray),
+            link: Style::default().fg(Color::LightCyan).add_modifier(Modifier::UNDERLINED),
+            highlight: Style::default().fg(Color::Black).bg(Color::White).add_modifier(Modifier::BOLD),
+            border: Style::default().fg(Color::White),
+            error: Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// Reduce the theme to the colors a terminal can show
+    ///
+    /// Without colors, emphasis is kept with bold, underline and reverse.
+    pub fn for_depth(mut self, depth: ColorDepth) -> Self {
+        self.background = depth.map(self.background).unwrap_or(Color::Reset);
+        for style in [
+            &mut self.text,
+            &mut self.heading,
+            &mut self.title,
+            &mut self.subtitle,
+            &mut self.accent,
+            &mut self.muted,
+            &mut self.link,
+            &mut self.highlight,
+            &mut self.border,
+            &mut self.error,
+        ] {
+            *style = depth.map_style(*style);
+        }
+
+        if depth == ColorDepth::Monochrome {
+            self.heading = self.heading.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            self.title = self.title.add_modifier(Modifier::BOLD);
+            self.accent = self.accent.add_modifier(Modifier::BOLD);
+            self.muted = self.muted.add_modifier(Modifier::DIM);
+            self.link = self.link.add_modifier(Modifier::UNDERLINED);
+            self.highlight = self.highlight.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+            self.error = self.error.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        }
+        self
+    }
+
+    /// Read a custom theme from a TOML or YAML file
+    ///
+    /// The theme starts from its `base` built-in theme (dark by default) and
+    /// is named after the file unless it sets `name`.
+    pub fn from_file(path: &Path, builtin: &[Theme]) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Could not read theme file {}", path.display()))?;
+        let file: ThemeFile = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(anyhow::Error::from),
+            _ => serde_yaml::from_str(&content).map_err(anyhow::Error::from),
+        }
+        .with_context(|| format!("Invalid theme file {}", path.display()))?;
+
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut theme = builtin
+            .iter()
+            .find(|theme| theme.name == base)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown base theme '{}' in {}", base, path.display()))?;
+        theme.name = match file.name {
+            Some(name) => name,
+            None => path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string(),
+        };
+
+        let parse = |spec: &Option<String>, style: &mut Style| -> Result<()> {
+            if let Some(spec) = spec {
+                *style = parse_style(spec).with_context(|| format!("Invalid style in {}", path.display()))?;
+            }
+            Ok(())
+        };
+        if let Some(ref background) = file.background {
+            theme.background = parse_color(background).with_context(|| format!("Invalid background in {}", path.display()))?;
+        }
+        parse(&file.text, &mut theme.text)?;
+        parse(&file.heading, &mut theme.heading)?;
+        parse(&file.title, &mut theme.title)?;
+        parse(&file.subtitle, &mut theme.subtitle)?;
+        parse(&file.accent, &mut theme.accent)?;
+        parse(&file.muted, &mut theme.muted)?;
+        parse(&file.link, &mut theme.link)?;
+        parse(&file.highlight, &mut theme.highlight)?;
+        parse(&file.border, &mut theme.border)?;
+        parse(&file.error, &mut theme.error)?;
+        Ok(theme)
+    }
+}
+
+/// Built-in and custom themes available for selection
+#[derive(Debug, Clone)]
+pub struct ThemeSet {
+    themes: Vec<Theme>,
+}
+
+impl ThemeSet {
+    /// Load the built-in themes and any custom themes from `dir`, reduced to `depth`
+    ///
+    /// A custom theme with the same name as a built-in one replaces it.
+    pub fn load(dir: Option<&Path>, depth: ColorDepth) -> Result<Self> {
+        let mut themes = vec![Theme::dark(), Theme::light(), Theme::high_contrast()];
+        let builtin = themes.clone();
+
+        if let Some(dir) = dir.filter(|dir| dir.is_dir()) {
+            let mut paths: Vec<_> = fs::read_dir(dir)
+                .with_context(|| format!("Could not read theme directory {}", dir.display()))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("toml" | "yml" | "yaml")))
+                .collect();
+            paths.sort();
+            for path in paths {
+                let theme = Theme::from_file(&path, &builtin)?;
+                match themes.iter_mut().find(|existing| existing.name == theme.name) {
+                    Some(existing) => *existing = theme,
+                    None => themes.push(theme),
+                }
+            }
+        }
+        let themes = themes.into_iter().map(|theme| theme.for_depth(depth)).collect();
+        Ok(Self { themes })
+    }
+
+    /// The same themes reduced to `depth`
+    pub fn for_depth(&self, depth: ColorDepth) -> Self {
+        let themes = self.themes.iter().map(|theme| theme.clone().for_depth(depth)).collect();
+        Self { themes }
+    }
+
+    /// Look up a theme by name
+    pub fn get(&self, name: &str) -> Result<&Theme> {
+        self.themes.iter().find(|theme| theme.name == name).ok_or_else(|| {
+            let names: Vec<&str> = self.themes.iter().map(|theme| theme.name.as_str()).collect();
+            anyhow!("Unknown theme '{}', expected one of: {}", name, names.join(", "))
+        })
+    }
+
+    /// Built-in dark or light theme, or the custom theme replacing it
+    pub fn for_darkmode(&self, darkmode: bool) -> &Theme {
+        let name = if darkmode { "dark" } else { "light" };
+        self.get(name).unwrap_or(&self.themes[0])
+    }
+
+    /// Theme named on the command line, or the one matching the portfolio's darkmode
+    pub fn select(&self, name: Option<&str>, darkmode: bool) -> Result<&Theme> {
+        match name {
+            Some(name) => self.get(name),
+            None => Ok(self.for_darkmode(darkmode)),
+        }
+    }
+
+    /// Theme following `name` in the list, wrapping around
+    pub fn next_after(&self, name: &str) -> &Theme {
+        let index = self.themes.iter().position(|theme| theme.name == name).map_or(0, |index| index + 1);
+        &self.themes[index % self.themes.len()]
+    }
+}
+
+/// Theme file contents, every field optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    name: Option<String>,
+    base: Option<String>,
+    background: Option<String>,
+    text: Option<String>,
+    heading: Option<String>,
+    title: Option<String>,
+    subtitle: Option<String>,
+    accent: Option<String>,
+    muted: Option<String>,
+    link: Option<String>,
+    highlight: Option<String>,
+    border: Option<String>,
+    error: Option<String>,
+}
+
+/// Parse a style such as `cyan bold` or `black on #5fafd7 underlined`
+fn parse_style(spec: &str) -> Result<Style> {
+    let mut style = Style::default();
+    let mut words = spec.split_whitespace();
+    while let Some(word) = words.next() {
+        let modifier = match word.to_lowercase().as_str() {
+            "bold" => Some(Modifier::BOLD),
+            "dim" => Some(Modifier::DIM),
+            "italic" => Some(Modifier::ITALIC),
+            "underline" | "underlined" => Some(Modifier::UNDERLINED),
+            "reversed" => Some(Modifier::REVERSED),
+            "crossed_out" | "strikethrough" => Some(Modifier::CROSSED_OUT),
+            _ => None,
+        };
+        if let Some(modifier) = modifier {
+            style = style.add_modifier(modifier);
+        } else if word == "on" {
+            let color = words.next().ok_or_else(|| anyhow!("Missing background color after 'on' in '{}'", spec))?;
+            style = style.bg(parse_color(color)?);
+        } else {
+            style = style.fg(parse_color(word)?);
+        }
+    }
+    Ok(style)
+}
+
+/// Parse a color name, `#rrggbb` value or 256-color index
+fn parse_color(name: &str) -> Result<Color> {
+    if name.eq_ignore_ascii_case("default") {
+        return Ok(Color::Reset);
+    }
+    Color::from_str(name).map_err(|_| anyhow!("Unknown color '{}'", name))
+}
@@ -1,13 +1,36 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::Frame;
 
-use crate::processor::formatter::FormattedPortfolio;
+use crate::processor::formatter::{FormattedContentItem, FormattedContentSection, FormattedPortfolio, TextCache};
+use crate::ui::icons::Icons;
+use crate::ui::mouse::{HitMap, HitTarget};
+use crate::ui::theme::Theme;
+
+/// Item selection and scroll position within a content section
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Selection {
+    /// Highlighted item
+    pub selected: usize,
+    /// First visible item, updated on render to keep the selection on screen
+    pub offset: usize,
+}
 
 /// Render a dynamic content section
-pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, section_index: usize) {
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    portfolio: &FormattedPortfolio,
+    section_index: usize,
+    selection: &mut Selection,
+    icons: &Icons,
+    hits: &mut HitMap,
+    theme: &Theme,
+    text_cache: &mut TextCache,
+) {
     // Get terminal size
     let terminal_width = area.width as usize;
     
@@ -18,7 +41,6 @@ pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, sec
             Constraint::Length(3),  // Title
             Constraint::Length(1),  // Separator
             Constraint::Min(10),    // Content
-            Constraint::Length(2),  // Navigation help
         ])
         .margin(1)  // Add margin to prevent text from touching the borders
         .split(area);
@@ -27,7 +49,7 @@ pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, sec
     if section_index >= portfolio.content_sections.len() {
         // Render error message if section index is out of bounds
         let error_message = Paragraph::new("Error: Content section not found")
-            .style(Style::default().fg(Color::Red))
+            .style(theme.error)
             .block(Block::default().borders(Borders::NONE));
         frame.render_widget(error_message, main_chunks[2]);
         return;
@@ -36,50 +58,41 @@ pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, sec
     let section = &portfolio.content_sections[section_index];
     
     // Render section title
-    let title = Paragraph::new(section.title.clone())
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+    let title = Paragraph::new(icons.section_label(&section.title))
+        .style(theme.heading)
         .block(Block::default().borders(Borders::NONE));
     frame.render_widget(title, main_chunks[0]);
     
     // Render separator
     let separator = "─".repeat(terminal_width);
     let separator_widget = Paragraph::new(separator)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(theme.muted)
         .block(Block::default().borders(Borders::NONE));
     frame.render_widget(separator_widget, main_chunks[1]);
     
     // Render content based on layout
     match section.layout.as_str() {
-        "list" => render_list_content(frame, main_chunks[2], section),
-        "text" => render_text_content(frame, main_chunks[2], section),
-        _ => render_default_content(frame, main_chunks[2], section),
+        "list" => render_list_content(frame, main_chunks[2], section, selection, icons, hits, theme, text_cache),
+        "text" => render_text_content(frame, main_chunks[2], section, theme, text_cache),
+        _ => render_default_content(frame, main_chunks[2], section, selection, icons, hits, theme, text_cache),
     }
-    
-    // Render navigation help
-    let nav_text = vec![
-        Line::from(vec![
-            Span::raw("Press "),
-            Span::styled("h", Style::default().fg(Color::Yellow)),
-            Span::raw(" for Home, "),
-            Span::styled("←/→", Style::default().fg(Color::Yellow)),
-            Span::raw(" to navigate sections, "),
-            Span::styled("0-9", Style::default().fg(Color::Yellow)),
-            Span::raw(" for direct section access, "),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::raw(" to quit"),
-        ]),
-    ];
-    let nav = Paragraph::new(nav_text)
-        .style(Style::default().fg(Color::White))
-        .block(Block::default().borders(Borders::NONE));
-    frame.render_widget(nav, main_chunks[3]);
 }
 
 /// Render content in list layout
-fn render_list_content(frame: &mut Frame, area: Rect, section: &crate::processor::formatter::FormattedContentSection) {
+#[allow(clippy::too_many_arguments)]
+fn render_list_content(
+    frame: &mut Frame,
+    area: Rect,
+    section: &FormattedContentSection,
+    selection: &mut Selection,
+    icons: &Icons,
+    hits: &mut HitMap,
+    theme: &Theme,
+    text_cache: &mut TextCache,
+) {
     if section.items.is_empty() {
         let empty_message = Paragraph::new("No items to display")
-            .style(Style::default().fg(Color::Gray))
+            .style(theme.muted)
             .block(Block::default().borders(Borders::NONE));
         frame.render_widget(empty_message, area);
         return;
@@ -89,6 +102,16 @@ fn render_list_content(frame: &mut Frame, area: Rect, section: &crate::processor
     let item_height = 6; // Approximate height for each item
     let visible_items = (area.height as usize / item_height).max(1);
     
+    // Scroll so the selected item is visible
+    let selected = selection.selected.min(section.items.len() - 1);
+    let offset = &mut selection.offset;
+    if selected < *offset {
+        *offset = selected;
+    } else if selected >= *offset + visible_items {
+        *offset = selected + 1 - visible_items;
+    }
+    *offset = (*offset).min(section.items.len() - 1);
+    
     // Create constraints for items
     let mut constraints = Vec::with_capacity(visible_items);
     for _ in 0..visible_items.min(section.items.len()) {
@@ -105,7 +128,7 @@ fn render_list_content(frame: &mut Frame, area: Rect, section: &crate::processor
         .split(area);
     
     // Render each visible item
-    for (i, item) in section.items.iter().enumerate().take(visible_items) {
+    for (i, (index, item)) in section.items.iter().enumerate().skip(*offset).take(visible_items).enumerate() {
         if i >= item_chunks.len() {
             break;
         }
@@ -116,14 +139,23 @@ fn render_list_content(frame: &mut Frame, area: Rect, section: &crate::processor
             .constraints([
                 Constraint::Length(1), // Title
                 Constraint::Length(1), // Subtitle/Caption
-                Constraint::Min(2),    // Description/Quote
+                Constraint::Min(1),    // Description/Quote
+                Constraint::Length(if item.links.is_empty() { 0 } else { 1 }), // Links
             ])
             .split(item_chunks[i]);
+        hits.push(item_chunks[i], HitTarget::Item(index));
         
         // Render title
         if !item.title.is_empty() {
-            let title = Paragraph::new(item.title.clone())
-                .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+            let mut style = theme.title;
+            if index == selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            if hits.is_hovered(&HitTarget::Item(index)) {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            let title = Paragraph::new(icons.label(&item.icon, &item.title))
+                .style(style)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(title, item_layout[0]);
         }
@@ -139,7 +171,7 @@ fn render_list_content(frame: &mut Frame, area: Rect, section: &crate::processor
         
         if !subtitle_text.is_empty() {
             let subtitle = Paragraph::new(subtitle_text)
-                .style(Style::default().fg(Color::Yellow))
+                .style(theme.subtitle)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(subtitle, item_layout[1]);
         }
@@ -154,22 +186,62 @@ fn render_list_content(frame: &mut Frame, area: Rect, section: &crate::processor
         };
         
         if !content_text.is_empty() {
-            // Create a paragraph with proper wrapping
+            // Wrap to the item width, reusing the last result
+            let content_text = text_cache.process_text(&content_text, item_layout[2].width as usize);
             let content = Paragraph::new(content_text)
-                .style(Style::default().fg(Color::White))
+                .style(theme.text)
                 .block(Block::default().borders(Borders::NONE))
                 .wrap(Wrap { trim: true });
             
             frame.render_widget(content, item_layout[2]);
         }
+        
+        // Render additional links
+        if !item.links.is_empty() {
+            let links = Paragraph::new(link_line(item, item_layout[3], icons, hits, theme))
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(links, item_layout[3]);
+        }
+    }
+}
+
+/// Build a line of icon-prefixed links for an item, recording each link in `hits`
+fn link_line<'a>(item: &FormattedContentItem, area: Rect, icons: &Icons, hits: &mut HitMap, theme: &Theme) -> Line<'a> {
+    let mut spans = Vec::new();
+    let mut x = area.x;
+    for link in &item.links {
+        if !spans.is_empty() {
+            spans.push(Span::raw("  "));
+            x += 2;
+        }
+        let target = HitTarget::Link(link.url.clone());
+        let mut style = theme.link;
+        if hits.is_hovered(&target) {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        let label = Span::styled(icons.label(&link.icon, &link.title), style);
+        let url = Span::styled(format!(" {}", link.url), theme.muted);
+        let width = (label.width() + url.width()) as u16;
+        let visible = width.min((area.x + area.width).saturating_sub(x));
+        hits.push(Rect { x, y: area.y, width: visible, height: 1 }, target);
+        x = x.saturating_add(width);
+        spans.push(label);
+        spans.push(url);
     }
+    Line::from(spans)
 }
 
 /// Render content in text layout
-fn render_text_content(frame: &mut Frame, area: Rect, section: &crate::processor::formatter::FormattedContentSection) {
+fn render_text_content(
+    frame: &mut Frame,
+    area: Rect,
+    section: &FormattedContentSection,
+    theme: &Theme,
+    text_cache: &mut TextCache,
+) {
     if section.items.is_empty() {
         let empty_message = Paragraph::new("No content to display")
-            .style(Style::default().fg(Color::Gray))
+            .style(theme.muted)
             .block(Block::default().borders(Borders::NONE));
         frame.render_widget(empty_message, area);
         return;
@@ -177,13 +249,13 @@ fn render_text_content(frame: &mut Frame, area: Rect, section: &crate::processor
     
     // For text layout, we just display the description of the first item
     let content_text = if !section.items[0].description.is_empty() {
-        section.items[0].description.clone()
+        text_cache.process_text(&section.items[0].description, area.width as usize)
     } else {
-        "No text content available".to_string()
+        "No text content available"
     };
     
     let content = Paragraph::new(content_text)
-        .style(Style::default().fg(Color::White))
+        .style(theme.text)
         .block(Block::default().borders(Borders::NONE))
         .wrap(Wrap { trim: true });
     
@@ -191,7 +263,17 @@ fn render_text_content(frame: &mut Frame, area: Rect, section: &crate::processor
 }
 
 /// Render content with default layout
-fn render_default_content(frame: &mut Frame, area: Rect, section: &crate::processor::formatter::FormattedContentSection) {
+#[allow(clippy::too_many_arguments)]
+fn render_default_content(
+    frame: &mut Frame,
+    area: Rect,
+    section: &FormattedContentSection,
+    selection: &mut Selection,
+    icons: &Icons,
+    hits: &mut HitMap,
+    theme: &Theme,
+    text_cache: &mut TextCache,
+) {
     // Default to list layout
-    render_list_content(frame, area, section);
+    render_list_content(frame, area, section, selection, icons, hits, theme, text_cache);
 }
\ No newline at end of file
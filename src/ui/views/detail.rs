@@ -0,0 +1,93 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::processor::formatter::{FormattedContentItem, TextCache};
+use crate::ui::icons::Icons;
+use crate::ui::mouse::{HitMap, HitTarget};
+use crate::ui::theme::Theme;
+use crate::ui::views::centered_rect;
+
+/// Render a single content item in full as a popup
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    item: &FormattedContentItem,
+    icons: &Icons,
+    scroll: &mut u16,
+    hits: &mut HitMap,
+    theme: &Theme,
+    text_cache: &mut TextCache,
+) {
+    let popup = centered_rect(80, 80, area);
+    frame.render_widget(Clear, popup);
+    hits.push(popup, HitTarget::Popup);
+
+    let title = if item.title.is_empty() { "Details".to_string() } else { icons.label(&item.icon, &item.title) };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(format!(" {} ", title))
+        .title_style(theme.heading);
+    let inner = block.inner(popup);
+    let lines = item_lines(item, icons, inner.width.max(1) as usize, theme, text_cache);
+
+    let max_scroll = (lines.len() as u16).saturating_sub(inner.height);
+    *scroll = (*scroll).min(max_scroll);
+
+    let detail = Paragraph::new(lines).style(theme.text).block(block).scroll((*scroll, 0));
+    frame.render_widget(detail, popup);
+}
+
+/// Subtitle, caption, text and links of an item wrapped to `width`
+pub fn item_lines(
+    item: &FormattedContentItem,
+    icons: &Icons,
+    width: usize,
+    theme: &Theme,
+    text_cache: &mut TextCache,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (text, style) in [
+        (&item.sub_title, theme.subtitle),
+        (&item.caption, theme.muted),
+    ] {
+        if !text.is_empty() {
+            lines.push(Line::from(Span::styled(text.clone(), style)));
+        }
+    }
+    if !item.description.is_empty() {
+        lines.push(Line::from(""));
+        push_wrapped(&mut lines, text_cache.process_text(&item.description, width), theme.text);
+    }
+    if !item.quote.is_empty() {
+        lines.push(Line::from(""));
+        let quote = format!("> {}", item.quote);
+        push_wrapped(&mut lines, text_cache.process_text(&quote, width), theme.muted.add_modifier(Modifier::ITALIC));
+    }
+
+    let mut links: Vec<(String, String)> = [(&item.url, "link"), (&item.link, "link")]
+        .into_iter()
+        .filter(|(url, _)| !url.is_empty())
+        .map(|(url, icon)| (icons.label(icon, "Link"), url.clone()))
+        .collect();
+    links.extend(item.links.iter().map(|link| (icons.label(&link.icon, &link.title), link.url.clone())));
+    if !links.is_empty() {
+        lines.push(Line::from(""));
+        for (label, url) in links {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", label), theme.link),
+                Span::raw(url),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Add wrapped text one line at a time, keeping paragraph breaks
+fn push_wrapped(lines: &mut Vec<Line<'static>>, text: &str, style: Style) {
+    lines.extend(text.split('\n').map(|line| Line::from(Span::styled(line.to_string(), style))));
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use ratatui::layout::{Alignment, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::data::error::LoadError;
+use crate::ui::keymap::{self, ERROR_KEYS};
+use crate::ui::theme::Theme;
+use crate::ui::views::centered_rect;
+
+/// Render the screen shown when the portfolio could not be loaded
+///
+/// `cache_age` is how old the cached copy of the source is, if there is one.
+/// `switch_source` offers to load a different source.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    source: &str,
+    error: &LoadError,
+    cache_age: Option<Duration>,
+    switch_source: bool,
+    theme: &Theme,
+) {
+    let popup = centered_rect(80, 70, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.error)
+        .title(format!(" {} ", error.title()))
+        .title_style(theme.error);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("Could not load the portfolio from", theme.heading)),
+        Line::from(Span::styled(source.to_string(), theme.link)),
+        Line::from(""),
+    ];
+    // Point out the offending line of invalid data
+    let snippet = match error {
+        LoadError::Parse { snippet, .. } => snippet.as_deref(),
+        _ => None,
+    };
+    for line in error.details() {
+        let style = if Some(line.as_str()) == snippet { theme.accent } else { theme.text };
+        lines.push(Line::from(Span::styled(line, style)));
+    }
+    lines.push(Line::from(""));
+
+    // List what can be done next
+    for (keys, description) in keymap::group_keys(ERROR_KEYS) {
+        if description == "Switch source" && !switch_source {
+            continue;
+        }
+        let description = if description == "Use cached data" {
+            match cache_age {
+                Some(age) => format!("{} saved {}", description, format_age(age)),
+                None => continue,
+            }
+        } else {
+            description.to_string()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(keys, theme.accent),
+            Span::raw(" "),
+            Span::styled(description, theme.text),
+        ]));
+    }
+
+    let error = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(block);
+    frame.render_widget(error, popup);
+}
+
+/// Describe how long ago something happened, such as `5 min ago`
+pub fn format_age(age: Duration) -> String {
+    let minutes = age.as_secs() / 60;
+    match minutes {
+        0 => "just now".to_string(),
+        1..=59 => format!("{} min ago", minutes),
+        60..=1439 => format!("{} h ago", minutes / 60),
+        _ => format!("{} days ago", minutes / 1440),
+    }
+}
@@ -0,0 +1,101 @@
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::actions::{Action, Context};
+use crate::ui::keymap::{self, Keymap, ERROR_KEYS, POPUP_KEYS, PROMPT_KEYS};
+use crate::ui::mouse::{HitMap, HitTarget};
+use crate::ui::theme::Theme;
+use crate::ui::views::centered_rect;
+
+/// Render the key binding help as a popup
+///
+/// The listing is built from the active keymap and the fixed prompt and
+/// popup and error screen key tables, the same sources the key handling uses.
+pub fn render(frame: &mut Frame, area: Rect, keymap: &Keymap, scroll: &mut u16, hits: &mut HitMap, theme: &Theme) {
+    let popup = centered_rect(70, 80, area);
+    frame.render_widget(Clear, popup);
+    hits.push(popup, HitTarget::Popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(" Help ")
+        .title_style(theme.heading);
+    let inner = block.inner(popup);
+
+    let lines = help_lines(keymap, theme);
+    let max_scroll = (lines.len() as u16).saturating_sub(inner.height);
+    *scroll = (*scroll).min(max_scroll);
+
+    let help = Paragraph::new(lines).style(theme.text).block(block).scroll((*scroll, 0));
+    frame.render_widget(help, popup);
+}
+
+/// Build the help listing grouped by context
+fn help_lines(keymap: &Keymap, theme: &Theme) -> Vec<Line<'static>> {
+    let mut groups = Vec::new();
+    for context in Context::ALL {
+        let entries: Vec<(String, String)> = match context {
+            Context::Prompt => keymap::group_keys(PROMPT_KEYS)
+                .into_iter()
+                .chain([("type".to_string(), "Edit the query")])
+                .map(|(keys, description)| (keys, description.to_string()))
+                .collect(),
+            Context::Popup => keymap::group_keys(POPUP_KEYS)
+                .into_iter()
+                .map(|(keys, description)| (keys, description.to_string()))
+                .collect(),
+            Context::Error => keymap::group_keys(ERROR_KEYS)
+                .into_iter()
+                .map(|(keys, description)| (keys, description.to_string()))
+                .collect(),
+            _ => {
+                let mut entries: Vec<(String, String)> = Action::ALL
+                    .iter()
+                    .filter(|action| action.context() == *context)
+                    .filter_map(|&action| keymap.key_hint(action).map(|keys| (keys, action.name())))
+                    .collect();
+                if *context == Context::Global {
+                    let sections: Vec<String> = (0..10)
+                        .filter_map(|i| keymap.key_hint(Action::GoToSection(i)))
+                        .collect();
+                    if !sections.is_empty() {
+                        entries.push((sections.join(" "), "Go to section by number".to_string()));
+                    }
+                    entries.push(("a-z".to_string(), "Go to section by first letter".to_string()));
+                }
+                entries
+            }
+        };
+
+        groups.push((context, entries));
+    }
+
+    // Size the key column to the longest key list
+    let key_width = groups
+        .iter()
+        .flat_map(|(_, entries)| entries.iter().map(|(keys, _)| keys.chars().count()))
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let mut lines = Vec::new();
+    for (context, entries) in groups {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            context.title(),
+            theme.heading,
+        )));
+        for (keys, description) in entries {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<width$}", keys, width = key_width), theme.accent),
+                Span::raw(description),
+            ]));
+        }
+    }
+    lines
+}
@@ -1,14 +1,28 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::Frame;
 
-use crate::processor::formatter::FormattedPortfolio;
+use crate::processor::formatter::{FormattedPortfolio, TextCache};
 use crate::ui::ascii_art;
+use crate::ui::icons::Icons;
+use crate::ui::mouse::{HitMap, HitTarget};
+use crate::ui::theme::Theme;
+
+/// Row of the header holding the social and additional links
+const LINKS_ROW: u16 = 4;
 
 /// Render the home view
-pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, content_sections: &[String]) {
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    portfolio: &FormattedPortfolio,
+    icons: &Icons,
+    hits: &mut HitMap,
+    theme: &Theme,
+    text_cache: &mut TextCache,
+) {
     // Get terminal size
     let terminal_width = area.width as usize;
     
@@ -19,7 +33,6 @@ pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, con
             Constraint::Length(8),  // Logo and header
             Constraint::Length(1),  // Separator
             Constraint::Min(10),    // Content
-            Constraint::Length(2),  // Navigation help
         ])
         .margin(1)  // Add margin to improve spacing
         .split(area);
@@ -35,43 +48,79 @@ pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, con
     
     // Render ASCII logo
     let logo = Paragraph::new(ascii_art::get_logo("duck"))
-        .style(Style::default().fg(Color::Yellow))
+        .style(theme.accent)
         .block(Block::default().borders(Borders::NONE));
     frame.render_widget(logo, header_chunks[0]);
     
+    // Collect social and additional links with their icons, recording where
+    // each one lands on the links row of the header
+    let links_area = Rect {
+        y: header_chunks[1].y + LINKS_ROW,
+        height: 1,
+        ..header_chunks[1]
+    };
+    let links = portfolio
+        .social
+        .iter()
+        .filter(|s| s.platform != "Email" && s.platform != "Website")
+        .map(|social| (icons.label(&social.platform, &social.username), &social.url))
+        .chain(portfolio.links.iter().map(|link| (icons.label(&link.icon, &link.title), &link.url)));
+    let mut link_spans = Vec::new();
+    let mut x = links_area.x;
+    for (label, url) in links {
+        if !link_spans.is_empty() {
+            link_spans.push(Span::raw("  "));
+            x += 2;
+        }
+        let target = HitTarget::Link(url.clone());
+        let mut style = theme.link;
+        if hits.is_hovered(&target) {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        let span = Span::styled(label, style);
+        let width = span.width() as u16;
+        if LINKS_ROW < header_chunks[1].height && !url.is_empty() {
+            let visible = width.min((links_area.x + links_area.width).saturating_sub(x));
+            hits.push(Rect { x, width: visible, ..links_area }, target);
+        }
+        x = x.saturating_add(width);
+        link_spans.push(span);
+    }
+    
     // Render title and name
     let name_title = vec![
         Line::from(vec![
             Span::raw(&portfolio.title),
             Span::raw(" "),
-            Span::styled(&portfolio.name, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(&portfolio.name, theme.heading),
         ]),
         Line::from(vec![Span::raw("")]),
         Line::from(vec![
-            Span::styled("Email: ", Style::default().fg(Color::Blue)),
+            Span::styled(format!("{}: ", icons.label("envelope", "Email")), theme.link),
             Span::raw(portfolio.social.iter().find(|s| s.platform == "Email").map_or_else(|| "N/A".to_string(), |s| s.username.clone())),
         ]),
         Line::from(vec![
-            Span::styled("Web: ", Style::default().fg(Color::Blue)),
+            Span::styled(format!("{}: ", icons.label("globe", "Web")), theme.link),
             Span::raw(portfolio.social.iter().find(|s| s.platform == "Website").map_or_else(|| "N/A".to_string(), |s| s.url.clone())),
         ]),
+        Line::from(link_spans),
     ];
     
     let name_title_widget = Paragraph::new(name_title)
-        .style(Style::default().fg(Color::White))
+        .style(theme.text)
         .block(Block::default().borders(Borders::NONE));
     frame.render_widget(name_title_widget, header_chunks[1]);
     
     // Render separator
     let separator = "─".repeat(terminal_width);
     let separator_widget = Paragraph::new(separator)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(theme.muted)
         .block(Block::default().borders(Borders::NONE));
     frame.render_widget(separator_widget, main_chunks[1]);
     
     // Render about section
-    let about_title = Paragraph::new("About Me")
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+    let about_title = Paragraph::new(icons.label("user", "About Me"))
+        .style(theme.heading)
         .block(Block::default().borders(Borders::NONE));
     
     let about_chunks = Layout::default()
@@ -85,50 +134,14 @@ pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio, con
     frame.render_widget(about_title, about_chunks[0]);
     
     // Calculate available width for text
-    let available_width = about_chunks[1].width as usize - 4; // Subtract some padding
+    let available_width = (about_chunks[1].width as usize).saturating_sub(4); // Subtract some padding
     
-    // Create a formatter with the appropriate width
-    let formatter = crate::processor::Formatter::with_max_width(available_width);
-    let processed_about = formatter.process_text(&portfolio.about);
+    // Format the about text for this width, reusing the last result
+    let processed_about = text_cache.process_text(&portfolio.about, available_width);
     
     let about_content = Paragraph::new(processed_about)
-        .style(Style::default().fg(Color::White))
+        .style(theme.text)
         .block(Block::default().borders(Borders::NONE))
         .wrap(Wrap { trim: true });
     frame.render_widget(about_content, about_chunks[1]);
-    
-    // Render navigation help
-    let mut nav_spans = vec![
-        Span::raw("Press "),
-        Span::styled("←/→", Style::default().fg(Color::Yellow)),
-        Span::raw(" to navigate, "),
-    ];
-    
-    // Add section shortcuts
-    for (i, section) in content_sections.iter().enumerate().take(10) {
-        if i > 0 {
-            nav_spans.push(Span::raw(", "));
-        }
-        
-        let key = if i == 0 {
-            "h".to_string()
-        } else if i < 10 {
-            i.to_string()
-        } else {
-            continue;
-        };
-        
-        nav_spans.push(Span::styled(key, Style::default().fg(Color::Yellow)));
-        nav_spans.push(Span::raw(format!(" for {}", section)));
-    }
-    
-    nav_spans.push(Span::raw(", "));
-    nav_spans.push(Span::styled("q", Style::default().fg(Color::Yellow)));
-    nav_spans.push(Span::raw(" to quit"));
-    
-    let nav_text = vec![Line::from(nav_spans)];
-    let nav = Paragraph::new(nav_text)
-        .style(Style::default().fg(Color::White))
-        .block(Block::default().borders(Borders::NONE));
-    frame.render_widget(nav, main_chunks[3]);
 }
\ No newline at end of file
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use ratatui::layout::{Alignment, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::ui::theme::Theme;
+use crate::ui::views::centered_rect;
+
+/// Render the progress screen shown while the portfolio loads
+pub fn render(frame: &mut Frame, area: Rect, source: &str, elapsed: Duration, spinner: &str, theme: &Theme) {
+    let popup = centered_rect(70, 40, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(" Loading ")
+        .title_style(theme.heading);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{} ", spinner), theme.accent),
+            Span::styled("Loading portfolio data", theme.text),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(source.to_string(), theme.link)),
+        Line::from(Span::styled(format!("{:.1}s", elapsed.as_secs_f32()), theme.muted)),
+    ];
+    let loading = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(block);
+    frame.render_widget(loading, popup);
+}
@@ -3,6 +3,23 @@ pub mod projects;
 pub mod skills;
 pub mod about;
 pub mod content;
+pub mod detail;
+pub mod error;
+pub mod help;
+pub mod loading;
+pub mod palette;
+pub mod prompt;
+pub mod qr;
+pub mod search;
+pub mod status;
+pub mod tabs;
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+
+use crate::ui::actions::Action;
+use crate::ui::keymap::Keymap;
+use crate::ui::theme::Theme;
 
 /// Available views
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,4 +28,50 @@ pub enum View {
     Home,
     /// Dynamic content view with section index
     Content(usize),
-}
\ No newline at end of file
+}
+
+/// Create a centered rectangle using percentages of the given area
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+/// Build a hint line listing the keys bound to each action in the active keymap
+pub fn help_line<'a>(keymap: &Keymap, entries: &[(Action, &'a str)], theme: &Theme) -> Line<'a> {
+    hint_line(
+        entries
+            .iter()
+            .filter_map(|&(action, label)| keymap.key_hint(action).map(|keys| (keys, label)))
+            .collect(),
+        theme,
+    )
+}
+
+/// Build a hint line from key labels and descriptions
+pub fn hint_line<'a>(entries: Vec<(String, &'a str)>, theme: &Theme) -> Line<'a> {
+    let mut spans = Vec::new();
+    for (keys, label) in entries {
+        if !spans.is_empty() {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled(keys, theme.accent));
+        spans.push(Span::raw(" "));
+        spans.push(Span::raw(label));
+    }
+    Line::from(spans)
+}
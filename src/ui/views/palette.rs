@@ -0,0 +1,70 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::palette::Palette;
+use crate::ui::theme::Theme;
+use crate::ui::views::centered_rect;
+
+/// Render the command palette as a popup
+pub fn render(frame: &mut Frame, area: Rect, palette: &Palette, theme: &Theme) {
+    let popup = centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(" Commands ")
+        .title_style(theme.heading);
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Query
+            Constraint::Min(0),    // Actions
+        ])
+        .split(inner);
+
+    // Render filter prompt
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled(":", theme.accent),
+        Span::styled(&palette.query, theme.text),
+        Span::styled("█", theme.muted),
+    ]));
+    frame.render_widget(prompt, chunks[0]);
+
+    // Render actions with their keys right-aligned
+    let width = chunks[1].width.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = palette
+        .filtered
+        .iter()
+        .map(|(entry, indices)| {
+            let keys = entry.keys.join(", ");
+            let mut spans: Vec<Span> = entry
+                .name
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    if indices.contains(&i) {
+                        Span::styled(c.to_string(), theme.accent.add_modifier(Modifier::BOLD))
+                    } else {
+                        Span::styled(c.to_string(), theme.text)
+                    }
+                })
+                .collect();
+            let used = entry.name.chars().count() + keys.chars().count();
+            spans.push(Span::raw(" ".repeat(width.saturating_sub(used).max(1))));
+            spans.push(Span::styled(keys, theme.muted));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(theme.highlight)
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(palette.current().map(|_| palette.selected));
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+}
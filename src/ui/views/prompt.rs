@@ -0,0 +1,35 @@
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::theme::Theme;
+
+/// Render a single-line text input as a popup
+pub fn render(frame: &mut Frame, area: Rect, title: &str, input: &str, theme: &Theme) {
+    let width = area.width.saturating_sub(4).min(80);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(3) / 2,
+        width,
+        height: 3.min(area.height),
+    };
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(format!(" {} ", title))
+        .title_style(theme.heading);
+
+    // Keep the end of long input visible
+    let visible = width.saturating_sub(4) as usize;
+    let skip = input.chars().count().saturating_sub(visible);
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled("> ", theme.accent),
+        Span::styled(input.chars().skip(skip).collect::<String>(), theme.text),
+        Span::styled("█", theme.muted),
+    ]))
+    .block(block);
+    frame.render_widget(prompt, popup);
+}
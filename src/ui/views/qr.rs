@@ -0,0 +1,111 @@
+use qrcode::{Color as Module, EcLevel, QrCode};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::ui::mouse::{HitMap, HitTarget};
+use crate::ui::theme::Theme;
+
+/// Light modules around the code that scanners need to find it
+const QUIET_ZONE: usize = 2;
+
+/// Something to show as a QR code
+#[derive(Debug, Clone)]
+pub struct QrTarget {
+    /// Popup title
+    pub label: String,
+    /// Encoded text
+    pub data: String,
+    /// Short description shown under the code
+    pub caption: String,
+}
+
+/// Render a QR code in a popup
+///
+/// Each character cell holds two modules stacked with half block characters.
+/// The code is always dark on light, whatever the theme, so phones can scan
+/// it. `position` is the index of the code and the number of codes to switch
+/// between.
+pub fn render(frame: &mut Frame, area: Rect, target: &QrTarget, position: (usize, usize), hits: &mut HitMap, theme: &Theme) {
+    let label = &target.label;
+    let (lines, width) = match QrCode::with_error_correction_level(&target.data, EcLevel::L) {
+        Ok(code) => {
+            let lines = code_lines(&code);
+            let width = (code.width() + 2 * QUIET_ZONE) as u16;
+            (lines, width)
+        }
+        Err(err) => (vec![Line::from(Span::styled(format!("Cannot encode {}: {}", label, err), theme.error))], 40),
+    };
+
+    // One line below the code shows what it encodes
+    let height = lines.len() as u16 + 1;
+    let popup_width = width.max(label.chars().count() as u16 + 12) + 2;
+    let popup_height = height + 2;
+    if popup_width > area.width || popup_height > area.height {
+        let message = format!("Enlarge the terminal to {}x{} to show this QR code", popup_width, popup_height);
+        let popup = Rect {
+            x: area.x,
+            y: area.y + area.height.saturating_sub(3) / 2,
+            width: area.width,
+            height: 3.min(area.height),
+        };
+        frame.render_widget(Clear, popup);
+        hits.push(popup, HitTarget::Popup);
+        let block = Block::default().borders(Borders::ALL).border_style(theme.border);
+        frame.render_widget(Paragraph::new(message).style(theme.error).wrap(Wrap { trim: true }).block(block), popup);
+        return;
+    }
+
+    let popup = Rect {
+        x: area.x + (area.width - popup_width) / 2,
+        y: area.y + (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+    frame.render_widget(Clear, popup);
+    hits.push(popup, HitTarget::Popup);
+
+    let (index, count) = position;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(format!(" {} ({}/{}) ", label, index + 1, count))
+        .title_style(theme.heading);
+
+    let mut lines = lines;
+    let caption: String = target.caption.chars().take(popup_width as usize - 2).collect();
+    lines.push(Line::from(Span::styled(caption, theme.muted)));
+    let qr = Paragraph::new(lines).style(theme.text).alignment(Alignment::Center).block(block);
+    frame.render_widget(qr, popup);
+}
+
+/// Lines of half blocks drawing the code with its quiet zone
+fn code_lines(code: &QrCode) -> Vec<Line<'static>> {
+    let width = code.width();
+    let modules = code.to_colors();
+    let dark = |x: usize, y: usize| {
+        let (Some(x), Some(y)) = (x.checked_sub(QUIET_ZONE), y.checked_sub(QUIET_ZONE)) else {
+            return false;
+        };
+        x < width && y < width && modules[y * width + x] == Module::Dark
+    };
+
+    let style = Style::default().fg(Color::Black).bg(Color::White);
+    let size = width + 2 * QUIET_ZONE;
+    (0..size)
+        .step_by(2)
+        .map(|y| {
+            let row: String = (0..size)
+                .map(|x| match (dark(x, y), dark(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect();
+            Line::from(Span::styled(row, style))
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::ui::search::{Search, SearchMatch};
+use crate::ui::theme::Theme;
+use crate::ui::views::centered_rect;
+
+/// Render the search prompt and results as a popup
+pub fn render(frame: &mut Frame, area: Rect, search: &Search, theme: &Theme) {
+    let popup = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(" Search ")
+        .title_style(theme.heading);
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Query
+            Constraint::Length(1), // Result count
+            Constraint::Min(0),    // Results
+        ])
+        .split(inner);
+
+    // Render query prompt
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled("/", theme.accent),
+        Span::styled(&search.query, theme.text),
+        Span::styled("█", theme.muted),
+    ]));
+    frame.render_widget(prompt, chunks[0]);
+
+    // Render result count
+    let count = if search.query.is_empty() {
+        "Type to search, Enter to jump, Esc to cancel".to_string()
+    } else {
+        format!("{} matches", search.results.len())
+    };
+    let count = Paragraph::new(count).style(theme.muted);
+    frame.render_widget(count, chunks[1]);
+
+    // Render results
+    let width = chunks[2].width as usize;
+    let items: Vec<ListItem> = search
+        .results
+        .iter()
+        .map(|result| {
+            ListItem::new(Text::from(vec![
+                highlighted_snippet(result, width.saturating_sub(2), theme),
+                Line::from(Span::styled(format!("  {}", result.breadcrumb), theme.muted)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(theme.highlight)
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(search.current().map(|_| search.selected));
+    frame.render_stateful_widget(list, chunks[2], &mut state);
+}
+
+/// Build a single line snippet around the first hit with matched characters highlighted
+fn highlighted_snippet(result: &SearchMatch, width: usize, theme: &Theme) -> Line<'static> {
+    let chars: Vec<char> = result.text.chars().collect();
+    let first = result.indices.first().copied().unwrap_or(0);
+    let start = if chars.len() > width { first.saturating_sub(width / 4) } else { 0 };
+    let end = (start + width).min(chars.len());
+
+    let normal = theme.text;
+    let hit = theme.accent.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    if start > 0 {
+        spans.push(Span::styled("…", theme.muted));
+    }
+    let mut run = String::new();
+    let mut run_is_hit = false;
+    for (i, c) in chars.iter().enumerate().take(end).skip(start) {
+        let is_hit = result.indices.binary_search(&i).is_ok();
+        if is_hit != run_is_hit && !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut run), if run_is_hit { hit } else { normal }));
+        }
+        run_is_hit = is_hit;
+        run.push(*c);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_is_hit { hit } else { normal }));
+    }
+    Line::from(spans)
+}
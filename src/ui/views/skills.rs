@@ -43,7 +43,7 @@ pub fn render(frame: &mut Frame, area: Rect, portfolio: &FormattedPortfolio) {
     for skill in &portfolio.skills {
         categories
             .entry(skill.category.clone())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(skill);
     }
 
@@ -0,0 +1,26 @@
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::ui::theme::Theme;
+
+/// Render the status line with key hints on the left and a message on the right
+pub fn render(frame: &mut Frame, area: Rect, hints: Line, message: Option<&str>, theme: &Theme) {
+    let message_width = message.map_or(0, |message| message.chars().count() as u16 + 1);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),                // Key hints
+            Constraint::Length(message_width), // Message
+        ])
+        .split(area);
+
+    frame.render_widget(Paragraph::new(hints).style(theme.text), chunks[0]);
+
+    if let Some(message) = message {
+        let message = Paragraph::new(Line::from(Span::styled(message.to_string(), theme.accent)))
+            .alignment(Alignment::Right);
+        frame.render_widget(message, chunks[1]);
+    }
+}
@@ -0,0 +1,131 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Tabs};
+use ratatui::Frame;
+
+use crate::ui::icons::{IconMode, Icons};
+use crate::ui::mouse::{HitMap, HitTarget};
+use crate::ui::theme::Theme;
+
+/// Width of the divider drawn between tabs
+const DIVIDER_WIDTH: u16 = 1;
+
+/// Width of the overflow arrows shown when tabs are scrolled
+const ARROW_WIDTH: u16 = 2;
+
+/// Read state of a section shown in the tab bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabMark {
+    /// Section has been visited and has not changed since
+    Seen,
+    /// Section has not been visited yet
+    Unread,
+    /// Section content changed on the last reload
+    Changed,
+}
+
+/// Render the section tab bar, scrolling it so the selected tab is visible
+///
+/// `offset` is the first visible tab and is updated in place. The visible
+/// tabs are recorded in `hits`.
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    sections: &[String],
+    marks: &[TabMark],
+    selected: usize,
+    offset: &mut usize,
+    icons: &Icons,
+    hits: &mut HitMap,
+    theme: &Theme,
+) {
+    if sections.is_empty() || area.width == 0 || area.height == 0 {
+        return;
+    }
+
+    let labels: Vec<Line> = sections
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            let label = tab_label(section, marks.get(i).copied().unwrap_or(TabMark::Seen), icons, theme);
+            if hits.is_hovered(&HitTarget::Tab(i)) {
+                label.patch_style(Style::default().add_modifier(Modifier::UNDERLINED))
+            } else {
+                label
+            }
+        })
+        .collect();
+    let widths: Vec<u16> = labels.iter().map(|label| label.width() as u16 + 2).collect();
+
+    // Scroll the tabs so the selected one fits, leaving room for the arrows
+    let available = area.width.saturating_sub(ARROW_WIDTH * 2);
+    let selected = selected.min(sections.len() - 1);
+    if selected < *offset {
+        *offset = selected;
+    }
+    while *offset < selected && span_width(&widths[*offset..=selected]) > available {
+        *offset += 1;
+    }
+    *offset = (*offset).min(sections.len() - 1);
+
+    let mut visible = 0;
+    while *offset + visible < sections.len() && span_width(&widths[*offset..=*offset + visible]) <= available {
+        visible += 1;
+    }
+    let visible = visible.max(1);
+    let end = *offset + visible;
+
+    // Overflow arrows
+    let ascii = icons.mode() == IconMode::Ascii;
+    let arrow_style = theme.muted;
+    if *offset > 0 {
+        let left = Paragraph::new(if ascii { "<" } else { "‹" }).style(arrow_style);
+        frame.render_widget(left, Rect { width: ARROW_WIDTH, ..area });
+    }
+    if end < sections.len() {
+        let right = Paragraph::new(if ascii { ">" } else { "›" }).style(arrow_style);
+        let x = area.x + area.width.saturating_sub(1);
+        frame.render_widget(right, Rect { x, width: 1, ..area });
+    }
+
+    let tabs_area = Rect {
+        x: area.x + ARROW_WIDTH,
+        width: available,
+        height: 1,
+        ..area
+    };
+    let tabs = Tabs::new(labels[*offset..end].to_vec())
+        .select(selected.saturating_sub(*offset))
+        .style(theme.text)
+        .highlight_style(theme.highlight)
+        .divider(Span::styled("│", theme.muted));
+    frame.render_widget(tabs, tabs_area);
+
+    // Record where each visible tab was drawn
+    let mut x = tabs_area.x;
+    for (index, width) in widths.iter().enumerate().take(end).skip(*offset) {
+        let width = (*width).min((tabs_area.x + tabs_area.width).saturating_sub(x));
+        hits.push(Rect { x, y: area.y, width, height: 1 }, HitTarget::Tab(index));
+        x += width + DIVIDER_WIDTH;
+    }
+}
+
+/// Build the label for a single tab
+fn tab_label<'a>(section: &str, mark: TabMark, icons: &Icons, theme: &Theme) -> Line<'a> {
+    let mut spans = vec![Span::raw(icons.section_label(section))];
+    let unread = if icons.mode() == IconMode::Ascii { " +" } else { " •" };
+    match mark {
+        TabMark::Seen => {}
+        TabMark::Unread => spans.push(Span::styled(unread, theme.link)),
+        TabMark::Changed => spans.push(Span::styled(" *", theme.accent.add_modifier(Modifier::BOLD))),
+    }
+    Line::from(spans)
+}
+
+/// Total width of a run of tabs including dividers
+fn span_width(widths: &[u16]) -> u16 {
+    let dividers = widths.len().saturating_sub(1) as u16 * DIVIDER_WIDTH;
+    widths.iter().sum::<u16>() + dividers
+}